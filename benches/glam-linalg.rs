@@ -23,6 +23,14 @@ macro_rules! eigvals_benches {
         v.chunks($num * $num).map([<DMat $num>]::from_cols_slice).collect()
       }
 
+      // The symmetric solvers expect symmetric input, so the random matrices are symmetrized as (A + Aᵀ) / 2
+      fn [<rand_dmat $num _symmetric_vec>]() -> Vec<[<DMat $num>]> {
+        [<rand_dmat $num _vec>]()
+          .into_iter()
+          .map(|A| 0.5 * (A + A.transpose()))
+          .collect()
+      }
+
       #[bench]
       fn [<eigvals $num>](bencher: Bencher) {
         let matrices = [<rand_dmat $num _vec>]();
@@ -35,9 +43,20 @@ macro_rules! eigvals_benches {
       }
 
       #[bench]
-      fn [<eigvals $num _symmetric>](bencher: Bencher) {
+      fn [<eig $num>](bencher: Bencher) {
         let matrices = [<rand_dmat $num _vec>]();
 
+        bencher.bench_local(move || {
+          for M in matrices.iter() {
+            black_box(black_box(M).eig());
+          }
+        });
+      }
+
+      #[bench]
+      fn [<eigvals $num _symmetric>](bencher: Bencher) {
+        let matrices = [<rand_dmat $num _symmetric_vec>]();
+
         bencher.bench_local(move || {
           for M in matrices.iter() {
             black_box(black_box(M).eigvals_symmetric());
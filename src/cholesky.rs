@@ -0,0 +1,305 @@
+use glam::{DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+
+/// The Cholesky decomposition A = L Lᵀ of a symmetric positive definite matrix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cholesky<M> {
+  /// The lower triangular factor, with a positive diagonal
+  pub L: M,
+}
+
+/// The decomposition P A Pᵀ = L D Lᵀ of a symmetric matrix with symmetric pivoting
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ldlt<M, const N: usize> {
+  /// The unit lower triangular factor
+  pub L: M,
+  /// The block diagonal factor, made of 1x1 blocks and 2x2 blocks with a nonzero off-diagonal entry
+  pub D: M,
+  /// The symmetric permutation, where row i of P A is row perm[i] of A
+  pub perm: [usize; N],
+  /// Whether a pivot of D is zero relative to the magnitude of A
+  pub singular: bool,
+}
+
+/// Computes the Cholesky factor of a symmetric matrix given as an array of columns, reading only its
+/// lower triangle. Returns None if the matrix is not positive definite
+pub(crate) fn cholesky<const N: usize>(A: [[f64; N]; N]) -> Option<[[f64; N]; N]> {
+  let mut L = [[0.0; N]; N];
+
+  for j in 0..N {
+    let d = A[j][j] - (0..j).map(|k| L[k][j] * L[k][j]).sum::<f64>();
+    // This also rejects NaN
+    if !(d > 0.0 && d.is_finite()) {
+      return None;
+    }
+
+    let d = d.sqrt();
+    L[j][j] = d;
+    for i in j + 1..N {
+      L[j][i] = (A[j][i] - (0..j).map(|k| L[k][i] * L[k][j]).sum::<f64>()) / d;
+    }
+  }
+
+  Some(L)
+}
+
+/// Solves L Lᵀ x = b by forward and back substitution
+pub(crate) fn cholesky_solve<const N: usize>(L: &[[f64; N]; N], b: [f64; N]) -> [f64; N] {
+  let mut x = b;
+
+  for j in 0..N {
+    x[j] /= L[j][j];
+    for i in j + 1..N {
+      x[i] -= L[j][i] * x[j];
+    }
+  }
+
+  for i in (0..N).rev() {
+    x[i] = (x[i] - (i + 1..N).map(|j| L[i][j] * x[j]).sum::<f64>()) / L[i][i];
+  }
+
+  x
+}
+
+// The Bunch-Kaufman constant (1 + √17) / 8, which bounds the element growth of the factorization
+const BUNCH_KAUFMAN_ALPHA: f64 = 0.6403882032022076;
+
+/// Computes the LDLᵀ decomposition of a symmetric matrix given as an array of columns, reading only its
+/// lower triangle
+// This is the Bunch-Kaufman partial pivoting strategy, which picks 2x2 pivots where a 1x1 pivot would
+// be too small relative to its column. This keeps the factorization stable for indefinite matrices
+pub(crate) fn ldlt<const N: usize>(A: [[f64; N]; N]) -> Ldlt<[[f64; N]; N], N> {
+  let mut A: [[f64; N]; N] =
+    std::array::from_fn(|j| std::array::from_fn(|i| if i >= j { A[j][i] } else { A[i][j] }));
+
+  let max = A.iter().flatten().fold(0.0_f64, |max, x| max.max(x.abs()));
+  let tol = N as f64 * f64::EPSILON * max;
+
+  let mut perm: [usize; N] = std::array::from_fn(|i| i);
+  let mut L = [[0.0; N]; N];
+  let mut D = [[0.0; N]; N];
+  // None of the pivot comparisons hold for NaN, so non-finite input is flagged before pivoting
+  let mut singular = !A.iter().flatten().all(|x| x.is_finite());
+
+  let mut k = 0;
+  while k < N {
+    let diag = A[k][k].abs();
+    let (r, colmax) = (k + 1..N)
+      .map(|i| (i, A[k][i].abs()))
+      .fold((k, 0.0), |max, x| if x.1 > max.1 { x } else { max });
+
+    // A 2x2 pivot needs a second row, which the last one lacks
+    let two_by_two = if k + 1 == N || diag >= BUNCH_KAUFMAN_ALPHA * colmax {
+      false
+    } else {
+      let rowmax = (k..N)
+        .filter(|&j| j != r)
+        .fold(0.0_f64, |max, j| max.max(A[j][r].abs()));
+
+      if diag * rowmax >= BUNCH_KAUFMAN_ALPHA * colmax * colmax {
+        false
+      } else if A[r][r].abs() >= BUNCH_KAUFMAN_ALPHA * rowmax {
+        symmetric_swap(&mut A, &mut L, &mut perm, k, r);
+        false
+      } else {
+        symmetric_swap(&mut A, &mut L, &mut perm, k + 1, r);
+        true
+      }
+    };
+
+    if !two_by_two {
+      let d = A[k][k];
+      D[k][k] = d;
+      L[k][k] = 1.0;
+      singular |= d.abs() <= tol;
+
+      if d != 0.0 {
+        for i in k + 1..N {
+          L[k][i] = A[k][i] / d;
+        }
+        let a = A[k];
+        for (j, col) in A.iter_mut().enumerate().skip(k + 1) {
+          for i in k + 1..N {
+            col[i] -= L[k][i] * a[j];
+          }
+        }
+      }
+
+      k += 1;
+    } else {
+      let (a, b, c) = (A[k][k], A[k][k + 1], A[k + 1][k + 1]);
+      D[k][k] = a;
+      D[k][k + 1] = b;
+      D[k + 1][k] = b;
+      D[k + 1][k + 1] = c;
+      L[k][k] = 1.0;
+      L[k + 1][k + 1] = 1.0;
+
+      // The block is indefinite with |b| = colmax dominating, so |ac - b²| is at least (1 - α²) b². Its
+      // smaller eigenvalue is then about det / b, which is negligible when colmax itself is tiny
+      let det = a * c - b * b;
+      singular |= det.abs() <= tol * colmax;
+
+      if det != 0.0 {
+        for i in k + 2..N {
+          let (x, y) = (A[k][i], A[k + 1][i]);
+          L[k][i] = (c * x - b * y) / det;
+          L[k + 1][i] = (a * y - b * x) / det;
+        }
+        let (u, v) = (A[k], A[k + 1]);
+        for (j, col) in A.iter_mut().enumerate().skip(k + 2) {
+          for i in k + 2..N {
+            col[i] -= L[k][i] * u[j] + L[k + 1][i] * v[j];
+          }
+        }
+      }
+
+      k += 2;
+    }
+  }
+
+  Ldlt {
+    L,
+    D,
+    perm,
+    singular,
+  }
+}
+
+// Swaps rows and columns i < j of the symmetric matrix A along with the rows of the computed part of L
+fn symmetric_swap<const N: usize>(
+  A: &mut [[f64; N]; N],
+  L: &mut [[f64; N]; N],
+  perm: &mut [usize; N],
+  i: usize,
+  j: usize,
+) {
+  if i == j {
+    return;
+  }
+
+  A.swap(i, j);
+  A.iter_mut().for_each(|col| col.swap(i, j));
+  L.iter_mut().for_each(|col| col.swap(i, j));
+  perm.swap(i, j);
+}
+
+/// Solves L D Lᵀ Pᵀ x = P b by substitution through each factor
+pub(crate) fn ldlt_solve<const N: usize>(
+  L: &[[f64; N]; N],
+  D: &[[f64; N]; N],
+  perm: &[usize; N],
+  b: [f64; N],
+) -> [f64; N] {
+  let mut y = perm.map(|i| b[i]);
+
+  for j in 0..N {
+    for i in j + 1..N {
+      y[i] -= L[j][i] * y[j];
+    }
+  }
+
+  let mut k = 0;
+  while k < N {
+    if k + 1 < N && D[k][k + 1] != 0.0 {
+      let (a, b, c) = (D[k][k], D[k][k + 1], D[k + 1][k + 1]);
+      let det = a * c - b * b;
+      let (u, v) = (y[k], y[k + 1]);
+      y[k] = (c * u - b * v) / det;
+      y[k + 1] = (a * v - b * u) / det;
+      k += 2;
+    } else {
+      y[k] /= D[k][k];
+      k += 1;
+    }
+  }
+
+  for i in (0..N).rev() {
+    y[i] -= (i + 1..N).map(|j| L[i][j] * y[j]).sum::<f64>();
+  }
+
+  let mut x = [0.0; N];
+  for (i, &p) in perm.iter().enumerate() {
+    x[p] = y[i];
+  }
+  x
+}
+
+macro_rules! impl_cholesky {
+  ($num:expr, $Mat:ty, $Vec:ty) => {
+    impl Cholesky<$Mat> {
+      pub(crate) fn new(A: $Mat) -> Option<Self> {
+        let L = cholesky(A.to_cols_array_2d())?;
+        Some(Self {
+          L: <$Mat>::from_cols_array_2d(&L),
+        })
+      }
+
+      /// Solves A x = b
+      pub fn solve(&self, b: $Vec) -> $Vec {
+        cholesky_solve(&self.L.to_cols_array_2d(), b.into()).into()
+      }
+
+      /// Solves A X = B for each column of B
+      pub fn solve_mat(&self, B: $Mat) -> $Mat {
+        let L = self.L.to_cols_array_2d();
+        let X = B.to_cols_array_2d().map(|b| cholesky_solve(&L, b));
+        <$Mat>::from_cols_array_2d(&X)
+      }
+    }
+
+    impl Ldlt<$Mat, $num> {
+      pub(crate) fn new(A: $Mat) -> Self {
+        let Ldlt {
+          L,
+          D,
+          perm,
+          singular,
+        } = ldlt(A.to_cols_array_2d());
+        Self {
+          L: <$Mat>::from_cols_array_2d(&L),
+          D: <$Mat>::from_cols_array_2d(&D),
+          perm,
+          singular,
+        }
+      }
+
+      /// Solves A x = b, or returns None if A is singular
+      pub fn solve(&self, b: $Vec) -> Option<$Vec> {
+        if self.singular {
+          return None;
+        }
+
+        let L = self.L.to_cols_array_2d();
+        let D = self.D.to_cols_array_2d();
+        Some(ldlt_solve(&L, &D, &self.perm, b.into()).into())
+      }
+
+      /// Solves A X = B for each column of B, or returns None if A is singular
+      pub fn solve_mat(&self, B: $Mat) -> Option<$Mat> {
+        if self.singular {
+          return None;
+        }
+
+        let L = self.L.to_cols_array_2d();
+        let D = self.D.to_cols_array_2d();
+        let X = B
+          .to_cols_array_2d()
+          .map(|b| ldlt_solve(&L, &D, &self.perm, b));
+        Some(<$Mat>::from_cols_array_2d(&X))
+      }
+
+      /// Returns the permutation matrix P with P A Pᵀ = L D Lᵀ
+      pub fn permutation(&self) -> $Mat {
+        let mut P = [[0.0; $num]; $num];
+        for (i, &j) in self.perm.iter().enumerate() {
+          P[j][i] = 1.0;
+        }
+        <$Mat>::from_cols_array_2d(&P)
+      }
+    }
+  };
+}
+
+impl_cholesky!(2, DMat2, DVec2);
+impl_cholesky!(3, DMat3, DVec3);
+impl_cholesky!(4, DMat4, DVec4);
@@ -0,0 +1,335 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A complex number re + i im
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct Complex<T> {
+  pub re: T,
+  pub im: T,
+}
+
+pub type Complex32 = Complex<f32>;
+pub type Complex64 = Complex<f64>;
+
+impl<T> Complex<T> {
+  #[inline]
+  pub const fn new(re: T, im: T) -> Self {
+    Self { re, im }
+  }
+}
+
+macro_rules! impl_complex {
+  ($T:ty) => {
+    impl Complex<$T> {
+      pub const ZERO: Self = Self::new(0.0, 0.0);
+      pub const ONE: Self = Self::new(1.0, 0.0);
+      pub const I: Self = Self::new(0.0, 1.0);
+
+      /// Creates a complex number from polar coordinates
+      #[inline]
+      pub fn from_polar(r: $T, theta: $T) -> Self {
+        let (sin, cos) = theta.sin_cos();
+        Self::new(r * cos, r * sin)
+      }
+
+      #[inline]
+      pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+      }
+
+      /// Returns the squared magnitude re² + im²
+      #[inline]
+      pub fn norm_sqr(self) -> $T {
+        self.re * self.re + self.im * self.im
+      }
+
+      /// Returns the magnitude, without undue overflow or underflow
+      #[inline]
+      pub fn abs(self) -> $T {
+        self.re.hypot(self.im)
+      }
+
+      /// Returns the argument in the range (-π, π]
+      #[inline]
+      pub fn arg(self) -> $T {
+        self.im.atan2(self.re)
+      }
+
+      #[inline]
+      pub fn recip(self) -> Self {
+        Self::ONE / self
+      }
+
+      /// Returns the principal square root, whose real part is non-negative
+      // The root is computed from the component with no cancellation, and the sign of the
+      // imaginary part follows im, so that the branch cut is continuous from above
+      pub fn sqrt(self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+          return Self::new(0.0, self.im);
+        }
+
+        let t = (0.5 * (self.re.abs() + self.abs())).sqrt();
+        if self.re >= 0.0 {
+          Self::new(t, 0.5 * self.im / t)
+        } else {
+          Self::new(0.5 * self.im.abs() / t, t.copysign(self.im))
+        }
+      }
+
+      #[inline]
+      pub fn exp(self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+      }
+
+      /// Returns the principal logarithm, whose imaginary part is in the range (-π, π]
+      #[inline]
+      pub fn ln(self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+      }
+
+      #[inline]
+      pub fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+      }
+
+      #[inline]
+      pub fn is_nan(self) -> bool {
+        self.re.is_nan() || self.im.is_nan()
+      }
+
+      /// Returns true if the components differ by at most max_abs_diff, like glam's abs_diff_eq
+      #[inline]
+      pub fn abs_diff_eq(self, rhs: Self, max_abs_diff: $T) -> bool {
+        (self.re - rhs.re).abs() <= max_abs_diff && (self.im - rhs.im).abs() <= max_abs_diff
+      }
+    }
+
+    impl From<$T> for Complex<$T> {
+      #[inline]
+      fn from(re: $T) -> Self {
+        Self::new(re, 0.0)
+      }
+    }
+
+    impl Neg for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+      }
+    }
+
+    impl Add for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+      }
+    }
+
+    impl Sub for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+      }
+    }
+
+    impl Mul for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn mul(self, rhs: Self) -> Self {
+        Self::new(
+          self.re * rhs.re - self.im * rhs.im,
+          self.re * rhs.im + self.im * rhs.re,
+        )
+      }
+    }
+
+    impl Div for Complex<$T> {
+      type Output = Self;
+
+      // Smith's algorithm, which scales by the larger component of rhs to avoid overflow
+      #[inline]
+      fn div(self, rhs: Self) -> Self {
+        if rhs.re.abs() >= rhs.im.abs() {
+          let r = rhs.im / rhs.re;
+          let d = rhs.re + rhs.im * r;
+          Self::new((self.re + self.im * r) / d, (self.im - self.re * r) / d)
+        } else {
+          let r = rhs.re / rhs.im;
+          let d = rhs.re * r + rhs.im;
+          Self::new((self.re * r + self.im) / d, (self.im * r - self.re) / d)
+        }
+      }
+    }
+
+    impl Add<$T> for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn add(self, rhs: $T) -> Self {
+        Self::new(self.re + rhs, self.im)
+      }
+    }
+
+    impl Sub<$T> for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn sub(self, rhs: $T) -> Self {
+        Self::new(self.re - rhs, self.im)
+      }
+    }
+
+    impl Mul<$T> for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn mul(self, rhs: $T) -> Self {
+        Self::new(self.re * rhs, self.im * rhs)
+      }
+    }
+
+    impl Div<$T> for Complex<$T> {
+      type Output = Self;
+
+      #[inline]
+      fn div(self, rhs: $T) -> Self {
+        Self::new(self.re / rhs, self.im / rhs)
+      }
+    }
+
+    impl Add<Complex<$T>> for $T {
+      type Output = Complex<$T>;
+
+      #[inline]
+      fn add(self, rhs: Complex<$T>) -> Complex<$T> {
+        rhs + self
+      }
+    }
+
+    impl Sub<Complex<$T>> for $T {
+      type Output = Complex<$T>;
+
+      #[inline]
+      fn sub(self, rhs: Complex<$T>) -> Complex<$T> {
+        Complex::new(self - rhs.re, -rhs.im)
+      }
+    }
+
+    impl Mul<Complex<$T>> for $T {
+      type Output = Complex<$T>;
+
+      #[inline]
+      fn mul(self, rhs: Complex<$T>) -> Complex<$T> {
+        rhs * self
+      }
+    }
+
+    impl Div<Complex<$T>> for $T {
+      type Output = Complex<$T>;
+
+      #[inline]
+      fn div(self, rhs: Complex<$T>) -> Complex<$T> {
+        Complex::from(self) / rhs
+      }
+    }
+
+    impl_assign!($T, AddAssign, add_assign, add);
+    impl_assign!($T, SubAssign, sub_assign, sub);
+    impl_assign!($T, MulAssign, mul_assign, mul);
+    impl_assign!($T, DivAssign, div_assign, div);
+
+    impl Sum for Complex<$T> {
+      fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+      }
+    }
+
+    impl<'a> Sum<&'a Self> for Complex<$T> {
+      fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+      }
+    }
+
+    impl Product for Complex<$T> {
+      fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+      }
+    }
+
+    impl<'a> Product<&'a Self> for Complex<$T> {
+      fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+      }
+    }
+
+    impl fmt::Display for Complex<$T> {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im.is_sign_negative() {
+          write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+          write!(f, "{}+{}i", self.re, self.im)
+        }
+      }
+    }
+
+    #[cfg(feature = "num-complex")]
+    impl From<num_complex::Complex<$T>> for Complex<$T> {
+      #[inline]
+      fn from(z: num_complex::Complex<$T>) -> Self {
+        Self::new(z.re, z.im)
+      }
+    }
+
+    #[cfg(feature = "num-complex")]
+    impl From<Complex<$T>> for num_complex::Complex<$T> {
+      #[inline]
+      fn from(z: Complex<$T>) -> Self {
+        Self::new(z.re, z.im)
+      }
+    }
+  };
+}
+
+macro_rules! impl_assign {
+  ($T:ty, $Trait:ident, $method:ident, $op:ident) => {
+    impl $Trait for Complex<$T> {
+      #[inline]
+      fn $method(&mut self, rhs: Self) {
+        *self = (*self).$op(rhs);
+      }
+    }
+
+    impl $Trait<$T> for Complex<$T> {
+      #[inline]
+      fn $method(&mut self, rhs: $T) {
+        *self = (*self).$op(rhs);
+      }
+    }
+  };
+}
+
+impl_complex!(f32);
+impl_complex!(f64);
+
+impl Complex32 {
+  #[inline]
+  pub fn as_complex64(self) -> Complex64 {
+    Complex64::new(self.re as f64, self.im as f64)
+  }
+}
+
+impl Complex64 {
+  #[inline]
+  pub fn as_complex32(self) -> Complex32 {
+    Complex32::new(self.re as f32, self.im as f32)
+  }
+}
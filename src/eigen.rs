@@ -1,221 +1,739 @@
+use crate::norms::norm_max;
 use crate::utils::*;
-use crate::DMatExt;
-
-use glam::{
-  dvec2, dvec3, dvec4, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, Vec3Swizzles, Vec4Swizzles,
+use crate::{
+  Complex64, DMatExt, EigenDiagnostics, EigenOptions, EigenOrder, Eigenvalue, LinalgError, Spectrum,
 };
 
-// These householder functions assume that a.x is nonzero
-fn householder2(a: DVec2) -> DMat2 {
-  let d = a.x + a.length().copysign(a.x);
-  let v = dvec2(1.0, a.y / d).normalize();
-  DMat2::IDENTITY - 2.0 * outer_product2(v, v)
+use glam::{dvec2, dvec3, dvec4, DMat2, DMat3, DMat4, Vec3Swizzles, Vec4Swizzles};
+
+/// Computes the sum and product of the two shifts of a double shift QR step on a hessenberg matrix
+// The matrix is given as an array of columns. The standard shifts are the eigenvalues of the trailing 2x2
+// block, while the exceptional shifts of LAPACK's dlahqr are built from the trailing subdiagonal entries
+pub(crate) fn shifts<T: Real, const N: usize>(A: &[[T; N]; N], exceptional: bool) -> (T, T) {
+  if exceptional {
+    let s = A[N - 2][N - 1].abs() + A[N - 3][N - 2].abs();
+    let h = T::from_f64(0.75) * s + A[N - 1][N - 1];
+    (T::from_f64(2.0) * h, h * h + T::from_f64(0.4375) * s * s)
+  } else {
+    let (a, b) = (A[N - 2][N - 2], A[N - 1][N - 2]);
+    let (c, d) = (A[N - 2][N - 1], A[N - 1][N - 1]);
+    (a + d, a * d - b * c)
+  }
 }
 
-fn householder3(a: DVec3) -> DMat3 {
-  let d = a.x + a.length().copysign(a.x);
-  let v = dvec3(1.0, a.y / d, a.z / d).normalize();
-  DMat3::IDENTITY - 2.0 * outer_product3(v, v)
+/// Computes the Householder reflector I - τ w wᵀ mapping v onto β e₁, returning (w, τ, β)
+// Here w = v - β e₁ with β = -sign(v₀) |v| to avoid cancellation, so that τ = 2 / |w|² = 1 / (β (β - v₀)).
+// The reflector is the identity, with τ = 0, if v already lies on the first axis.
+// The bulge entries shrink towards convergence, and once their squares underflow, most readily in f32,
+// τ would be left infinite
+fn reflector<T: Real, const M: usize>(v: [T; M]) -> ([T; M], T, T) {
+  let alpha = v[0];
+  let norm_sq: T = v[1..].iter().map(|&x| x * x).sum();
+  if norm_sq == T::ZERO {
+    return (v, T::ZERO, alpha);
+  }
+
+  // Squares this small have lost precision to underflow, so v is scaled by its largest entry
+  let sum_sq = alpha * alpha + norm_sq;
+  let (mut v, scale, sum_sq) = if sum_sq < T::MIN_POSITIVE / T::EPSILON {
+    let scale = v.iter().fold(T::ZERO, |max, x| max.max(x.abs()));
+    let v = v.map(|x| x / scale);
+    (v, scale, v.iter().map(|&x| x * x).sum())
+  } else {
+    (v, T::ONE, sum_sq)
+  };
+
+  let beta = -sum_sq.sqrt().copysign(v[0]);
+  let tau = (beta * (beta - v[0])).recip();
+  v[0] -= beta;
+  (v, tau, beta * scale)
 }
 
-fn householder4(a: DVec4) -> DMat4 {
-  let d = a.x + a.length().copysign(a.x);
-  let v = dvec4(1.0, a.y / d, a.z / d, a.w / d).normalize();
-  DMat4::IDENTITY - 2.0 * outer_product4(v, v)
+/// Applies the reflector I - τ u uᵀ from the left to rows k..k + M of the columns from c onwards
+// The rows and columns are taken as fixed size arrays, so that the loops over them unroll without bounds checks
+fn reflect_rows<T: Real, const N: usize, const M: usize>(
+  A: &mut [[T; N]; N],
+  k: usize,
+  c: usize,
+  u: &[T; M],
+  tau: T,
+) {
+  for col in A.iter_mut().skip(c) {
+    let rows: &mut [T; M] = (&mut col[k..k + M]).try_into().unwrap();
+    let w = tau * (0..M).fold(T::ZERO, |w, m| w + rows[m] * u[m]);
+    (0..M).for_each(|m| rows[m] -= w * u[m]);
+  }
 }
 
-/// Computes a QR decomposition of a 3x3 hessenberg matrix
-// These functions could be made more efficient by taking advantage of the hessenberg form of the inputs matrix
-fn qr3(A: DMat3) -> (DMat3, DMat3) {
-  let mut A = A;
-  let mut Q = DMat3::IDENTITY;
+/// Applies a reflector from the right to columns k..k + M of the rows before r
+fn reflect_cols<T: Real, const N: usize, const M: usize>(
+  A: &mut [[T; N]; N],
+  k: usize,
+  r: usize,
+  u: &[T; M],
+  tau: T,
+) {
+  let cols: &mut [[T; N]; M] = (&mut A[k..k + M]).try_into().unwrap();
+  (0..r.min(N)).for_each(|i| {
+    let w = tau * (0..M).fold(T::ZERO, |w, m| w + cols[m][i] * u[m]);
+    (0..M).for_each(|m| cols[m][i] -= w * u[m]);
+  });
+}
 
-  let H = householder3(A.x_axis);
-  Q *= H;
-  A = H * A;
+/// Performs an implicit double shift QR step on an NxN hessenberg matrix, given as an array of columns
+// Francis' algorithm, as in Golub & Van Loan's Algorithm 7.5.1: the reflector taking the first column of
+// A² - sA + tI onto the first axis creates a bulge below the subdiagonal, which is then chased off the bottom
+// of the matrix with 3-element reflectors. This costs O(N²) per step instead of forming A² and a dense QR
+// decomposition. The transforms are accumulated into the columns of Z if given
+pub(crate) fn francis_step<T: Real, const N: usize>(
+  A: &mut [[T; N]; N],
+  mut Z: Option<&mut [[T; N]; N]>,
+  s: T,
+  t: T,
+) {
+  let mut v = [
+    A[0][0] * A[0][0] + A[1][0] * A[0][1] - s * A[0][0] + t,
+    A[0][1] * (A[0][0] + A[1][1] - s),
+    A[0][1] * A[1][2],
+  ];
+
+  for k in 0..N - 2 {
+    let (u, tau, beta) = reflector(v);
+    reflect_rows(A, k, k.saturating_sub(1), &u, tau);
+    reflect_cols(A, k, (k + 4).min(N), &u, tau);
+    if let Some(Z) = Z.as_deref_mut() {
+      reflect_cols(Z, k, N, &u, tau);
+    }
 
-  let H = householder2(A.y_axis.yz());
-  let H = DMat3::from_cols_array(&[
-    1.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, 0.0, H.x_axis.y, H.y_axis.y,
-  ]);
-  Q *= H;
-  A = H * A;
+    // The reflector clears the bulge in column k - 1, which is set exactly to keep the hessenberg form
+    if k > 0 {
+      A[k - 1][k] = beta;
+      A[k - 1][k + 1] = T::ZERO;
+      A[k - 1][k + 2] = T::ZERO;
+    }
 
-  (Q, A)
-}
+    v = [
+      A[k][k + 1],
+      A[k][k + 2],
+      if k + 3 < N { A[k][k + 3] } else { T::ZERO },
+    ];
+  }
 
-/// Computes a QR decomposition of a 4x4 hessenberg matrix
-fn qr4(A: DMat4) -> (DMat4, DMat4) {
-  let mut A = A;
-  let mut Q = DMat4::IDENTITY;
-
-  let H = householder4(A.x_axis);
-  Q *= H;
-  A = H * A;
-
-  let H = householder3(A.y_axis.yzw());
-  let H = DMat4::from_cols_array(&[
-    1.0, 0.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, H.z_axis.x, 0.0, H.x_axis.y, H.y_axis.y,
-    H.z_axis.y, 0.0, H.x_axis.z, H.y_axis.z, H.z_axis.z,
-  ]);
-  Q *= H;
-  A = H * A;
-
-  let H = householder2(A.z_axis.zw());
-  let H = DMat4::from_cols_array(&[
-    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, 0.0, 0.0, H.x_axis.y,
-    H.y_axis.y,
-  ]);
-  Q *= H;
-  A = H * A;
-
-  (Q, A)
+  let (u, tau, beta) = reflector([v[0], v[1]]);
+  reflect_rows(A, N - 2, N - 3, &u, tau);
+  reflect_cols(A, N - 2, N, &u, tau);
+  if let Some(Z) = Z {
+    reflect_cols(Z, N - 2, N, &u, tau);
+  }
+  A[N - 3][N - 2] = beta;
+  A[N - 3][N - 1] = T::ZERO;
 }
 
-// Computes the hessenberg form of a matrix
-pub(crate) fn hessenberg3(A: DMat3) -> DMat3 {
-  let mut v = A.x_axis.yz();
-  let alpha = -v.length() * v.x.signum();
-  v.x -= alpha;
-  v = v.normalize_or_zero();
-
-  let Q = DMat2::from_mat3_minor(A, 0, 0);
-  let Q = Q - 2.0 * outer_product2(v, lmul2(v, Q));
-
-  let mut A = DMat3::from_cols_array(&[
-    A.x_axis.x, alpha, 0.0, A.y_axis.x, Q.x_axis.x, Q.x_axis.y, A.z_axis.x, Q.y_axis.x, Q.y_axis.y,
-  ]);
-
-  let V = dvec3(
-    A.row(0).yz().dot(v),
-    A.row(1).yz().dot(v),
-    A.row(2).yz().dot(v),
-  );
-  A.y_axis -= 2.0 * V * v.x;
-  A.z_axis -= 2.0 * V * v.y;
-
-  A
+/// Copies the MxM block of A on the diagonal from row and column k
+pub(crate) fn diagonal_block<T: Real, const N: usize, const M: usize>(
+  A: &[[T; N]; N],
+  k: usize,
+) -> [[T; M]; M] {
+  std::array::from_fn(|j| std::array::from_fn(|i| A[k + j][k + i]))
 }
 
-pub(crate) fn hessenberg4(A: DMat4) -> DMat4 {
-  let mut v = A.x_axis.yzw();
-  let alpha = -v.length() * v.x.signum();
-  v.x -= alpha;
-  v = v.normalize_or_zero();
-
-  let Q = DMat3::from_mat4_minor(A, 0, 0);
-  let Q = Q - 2.0 * outer_product3(v, lmul3(v, Q));
-
-  let mut A = DMat4::from_cols_array(&[
-    A.x_axis.x, alpha, 0.0, 0.0, A.y_axis.x, Q.x_axis.x, Q.x_axis.y, Q.x_axis.z, A.z_axis.x,
-    Q.y_axis.x, Q.y_axis.y, Q.y_axis.z, A.w_axis.x, Q.z_axis.x, Q.z_axis.y, Q.z_axis.z,
-  ]);
-
-  let V = dvec4(
-    A.row(0).yzw().dot(v),
-    A.row(1).yzw().dot(v),
-    A.row(2).yzw().dot(v),
-    A.row(3).yzw().dot(v),
-  );
-  A.y_axis -= 2.0 * V * v.x;
-  A.z_axis -= 2.0 * V * v.y;
-  A.w_axis -= 2.0 * V * v.z;
-
-  let mut v = A.y_axis.zw();
-  let alpha = -v.length() * v.x.signum();
-  v.x -= alpha;
-  v = v.normalize_or_zero();
-
-  let Q = DMat2::from_cols(A.z_axis.zw(), A.w_axis.zw());
-  let Q = Q - 2.0 * outer_product2(v, lmul2(v, Q));
-
-  let mut A = DMat4::from_cols_array(&[
-    A.x_axis.x, A.x_axis.y, A.x_axis.z, A.x_axis.w, A.y_axis.x, A.y_axis.y, alpha, 0.0, A.z_axis.x,
-    A.z_axis.y, Q.x_axis.x, Q.x_axis.y, A.w_axis.x, A.w_axis.y, Q.y_axis.x, Q.y_axis.y,
-  ]);
-
-  let V = dvec4(
-    A.row(0).zw().dot(v),
-    A.row(1).zw().dot(v),
-    A.row(2).zw().dot(v),
-    A.row(3).zw().dot(v),
-  );
-  A.z_axis -= 2.0 * V * v.x;
-  A.w_axis -= 2.0 * V * v.y;
-
-  A
-}
+// The algorithms on glam types, generated for the f64 types here and for the f32 types in the single module
+macro_rules! impl_eigen {
+  (
+    $T:ident, $Complex:ident, $Mat2:ident, $Mat3:ident, $Mat4:ident, $vec2:ident, $vec3:ident,
+    $vec4:ident
+  ) => {
+    // Computes the hessenberg form H of a matrix, along with the orthogonal Q such that A = Q H Qᵀ
+    // The matrix is first normalized, see normalization, and H is scaled back afterwards
+    // Only the f64 matrices expose the hessenberg form, so the f32 instantiations go unused
+    #[allow(dead_code)]
+    pub(crate) fn hessenberg3(A: $Mat3) -> ($Mat3, $Mat3) {
+      let scale = normalization(A.norm_max());
+      let (Q, H) = hessenberg3_normalized(A / scale);
+      (Q, H * scale)
+    }
 
-/// Computes the eigenvalues of a dense 2x2 matrix
-// Direct solving of characteristic polynomial
-pub(crate) fn eigvals2(A: DMat2) -> [DVec2; 2] {
-  let t = A.trace();
-  let d = A.determinant();
-  let center = complex(0.5 * t);
-  let gap = csqrt(0.25 * t * t - d);
-  [center - gap, center + gap]
-}
+    #[allow(dead_code)]
+    pub(crate) fn hessenberg4(A: $Mat4) -> ($Mat4, $Mat4) {
+      let scale = normalization(A.norm_max());
+      let (Q, H) = hessenberg4_normalized(A / scale);
+      (Q, H * scale)
+    }
+
+    // The reductions of hessenberg3 and hessenberg4, for matrices the caller has already normalized
+    fn hessenberg3_normalized(A: $Mat3) -> ($Mat3, $Mat3) {
+      let mut v = A.x_axis.yz();
+      let alpha = -v.length() * v.x.signum();
+      v.x -= alpha;
+      v = v.normalize_or_zero();
+
+      let Q = $Mat2::from_mat3_minor(A, 0, 0);
+      let Q = Q - 2.0 * outer_product2(v, lmul2(v, Q));
+
+      let mut A = $Mat3::from_cols_array(&[
+        A.x_axis.x, alpha, 0.0, A.y_axis.x, Q.x_axis.x, Q.x_axis.y, A.z_axis.x, Q.y_axis.x,
+        Q.y_axis.y,
+      ]);
+
+      let V = $vec3(
+        A.row(0).yz().dot(v),
+        A.row(1).yz().dot(v),
+        A.row(2).yz().dot(v),
+      );
+      A.y_axis -= 2.0 * V * v.x;
+      A.z_axis -= 2.0 * V * v.y;
+
+      let P = embed_lower2_3($Mat2::IDENTITY - 2.0 * outer_product2(v, v));
+
+      (P, A)
+    }
 
-/// Computes the eigenvalues of a 3x3 hessenberg matrix
-// The eigvals*_hessenberg functions use the QR algorithm to determine the eigenvalues
-pub(crate) fn eigvals3_hessenberg(mut A: DMat3) -> [DVec2; 3] {
-  const CUTOFF: f64 = 1e-14;
+    fn hessenberg4_normalized(A: $Mat4) -> ($Mat4, $Mat4) {
+      let mut v = A.x_axis.yzw();
+      let alpha = -v.length() * v.x.signum();
+      v.x -= alpha;
+      v = v.normalize_or_zero();
+
+      let Q = $Mat3::from_mat4_minor(A, 0, 0);
+      let Q = Q - 2.0 * outer_product3(v, lmul3(v, Q));
+
+      let mut A = $Mat4::from_cols_array(&[
+        A.x_axis.x, alpha, 0.0, 0.0, A.y_axis.x, Q.x_axis.x, Q.x_axis.y, Q.x_axis.z, A.z_axis.x,
+        Q.y_axis.x, Q.y_axis.y, Q.y_axis.z, A.w_axis.x, Q.z_axis.x, Q.z_axis.y, Q.z_axis.z,
+      ]);
+
+      let V = $vec4(
+        A.row(0).yzw().dot(v),
+        A.row(1).yzw().dot(v),
+        A.row(2).yzw().dot(v),
+        A.row(3).yzw().dot(v),
+      );
+      A.y_axis -= 2.0 * V * v.x;
+      A.z_axis -= 2.0 * V * v.y;
+      A.w_axis -= 2.0 * V * v.z;
+
+      let P1 = embed_lower3_4($Mat3::IDENTITY - 2.0 * outer_product3(v, v));
+
+      let mut v = A.y_axis.zw();
+      let alpha = -v.length() * v.x.signum();
+      v.x -= alpha;
+      v = v.normalize_or_zero();
+
+      let Q = $Mat2::from_cols(A.z_axis.zw(), A.w_axis.zw());
+      let Q = Q - 2.0 * outer_product2(v, lmul2(v, Q));
+
+      let mut A = $Mat4::from_cols_array(&[
+        A.x_axis.x, A.x_axis.y, A.x_axis.z, A.x_axis.w, A.y_axis.x, A.y_axis.y, alpha, 0.0,
+        A.z_axis.x, A.z_axis.y, Q.x_axis.x, Q.x_axis.y, A.w_axis.x, A.w_axis.y, Q.y_axis.x,
+        Q.y_axis.y,
+      ]);
+
+      let V = $vec4(
+        A.row(0).zw().dot(v),
+        A.row(1).zw().dot(v),
+        A.row(2).zw().dot(v),
+        A.row(3).zw().dot(v),
+      );
+      A.z_axis -= 2.0 * V * v.x;
+      A.w_axis -= 2.0 * V * v.y;
+
+      let P2 = embed_lower2_4($Mat2::IDENTITY - 2.0 * outer_product2(v, v));
+
+      (P1 * P2, A)
+    }
 
-  // We shouldn't have more than a couple dozen iterations
-  for _ in 0..100 {
-    // If some subdiagonal element is small enough, deflate
-    if A.x_axis.y.abs() <= CUTOFF * (A.x_axis.x.abs() + A.y_axis.y.abs()) {
-      let [a, b] = eigvals2(DMat2::from_mat3_minor(A, 0, 0));
-      return [a, b, complex(A.x_axis.x)];
+    /// Computes the eigenvalues of a dense 2x2 matrix
+    // Direct solving of characteristic polynomial, whose negative discriminant gives a conjugate pair
+    // The matrix is first normalized, see normalization
+    pub(crate) fn eigvals2(A: $Mat2) -> Spectrum<$T, 2> {
+      let scale = normalization(A.norm_max());
+      let A = A / scale;
+      let [[a, c], [b, d]] = A.to_cols_array_2d();
+      let center = 0.5 * (a + d);
+      let disc = (0.5 * (a - d)) * (0.5 * (a - d)) + b * c;
+
+      let mut spectrum = Spectrum::new();
+      if disc < 0.0 {
+        spectrum.push(Eigenvalue::ConjugatePair {
+          re: center,
+          im: (-disc).sqrt(),
+        });
+      } else {
+        // The root of larger magnitude is formed without cancellation, and the other from the determinant,
+        // so that triangular blocks give back their diagonal
+        let large = center + disc.sqrt().copysign(center);
+        let small = if large == 0.0 {
+          0.0
+        } else {
+          (a / large) * d - (b / large) * c
+        };
+        spectrum.push(Eigenvalue::Real(large.min(small)));
+        spectrum.push(Eigenvalue::Real(large.max(small)));
+      }
+      spectrum.map(|x| x * scale)
     }
-    if A.y_axis.z.abs() <= CUTOFF * (A.y_axis.y.abs() + A.z_axis.z.abs()) {
-      let [a, b] = eigvals2(DMat2::from_mat3(A));
-      return [a, b, complex(A.z_axis.z)];
+
+    /// Computes the eigenvalues of a 3x3 hessenberg matrix given as an array of columns
+    // The eigvals*_hessenberg functions use the QR algorithm to determine the eigenvalues. The Francis steps
+    // work on the array in place, and only the deflated blocks are read back into glam types
+    pub(crate) fn eigvals3_hessenberg(
+      mut H: [[$T; 3]; 3],
+      options: &EigenOptions,
+    ) -> Result<(Spectrum<$T, 3>, EigenDiagnostics<3>), LinalgError> {
+      for i in 0..=options.max_iterations {
+        // If some subdiagonal element is small enough, deflate
+        let mut spectrum = Spectrum::new();
+        let subdiagonal = [H[0][1], H[1][2]].map(|x| x.abs().to_f64());
+        if options.is_negligible(H[0][1], H[0][0], H[1][1]) {
+          spectrum.push(Eigenvalue::Real(H[0][0]));
+          spectrum.extend(eigvals2($Mat2::from_cols_array_2d(&diagonal_block(&H, 1))));
+          return Ok((spectrum.sorted(), EigenDiagnostics::new(i, &subdiagonal)));
+        }
+        if options.is_negligible(H[1][2], H[1][1], H[2][2]) {
+          spectrum.extend(eigvals2($Mat2::from_cols_array_2d(&diagonal_block(&H, 0))));
+          spectrum.push(Eigenvalue::Real(H[2][2]));
+          return Ok((spectrum.sorted(), EigenDiagnostics::new(i, &subdiagonal)));
+        }
+
+        // The deflation checks also run once after the last iteration
+        if i == options.max_iterations {
+          break;
+        }
+
+        let (s, t) = shifts(&H, options.is_exceptional(i));
+        francis_step(&mut H, None, s, t);
+      }
+
+      Err(LinalgError::NoConvergence {
+        iterations: options.max_iterations,
+      })
     }
 
-    // Double shift QR step
-    let s = A.y_axis.y + A.z_axis.z;
-    let t = A.y_axis.y * A.z_axis.z - A.y_axis.z * A.z_axis.y;
+    /// Computes the eigenvalues of a 4x4 hessenberg matrix given as an array of columns
+    pub(crate) fn eigvals4_hessenberg(
+      mut H: [[$T; 4]; 4],
+      options: &EigenOptions,
+    ) -> Result<(Spectrum<$T, 4>, EigenDiagnostics<4>), LinalgError> {
+      for i in 0..=options.max_iterations {
+        // If some subdiagonal element is small enough, deflate
+        let mut spectrum = Spectrum::new();
+        let [a, b, c] = [H[0][1], H[1][2], H[2][3]].map(|x| x.abs().to_f64());
+        if options.is_negligible(H[0][1], H[0][0], H[1][1]) {
+          let (inner, diagnostics) =
+            eigvals3_hessenberg(diagonal_block(&H, 1), options).map_err(|e| e.after(i))?;
+          let [b, c] = [diagnostics.subdiagonal()[0], diagnostics.subdiagonal()[1]];
+          spectrum.push(Eigenvalue::Real(H[0][0]));
+          spectrum.extend(inner);
+          let diagnostics = EigenDiagnostics::new(i + diagnostics.iterations, &[a, b, c]);
+          return Ok((spectrum.sorted(), diagnostics));
+        }
+        if options.is_negligible(H[1][2], H[1][1], H[2][2]) {
+          spectrum.extend(eigvals2($Mat2::from_cols_array_2d(&diagonal_block(&H, 0))));
+          spectrum.extend(eigvals2($Mat2::from_cols_array_2d(&diagonal_block(&H, 2))));
+          return Ok((spectrum.sorted(), EigenDiagnostics::new(i, &[a, b, c])));
+        }
+        if options.is_negligible(H[2][3], H[2][2], H[3][3]) {
+          let (inner, diagnostics) =
+            eigvals3_hessenberg(diagonal_block(&H, 0), options).map_err(|e| e.after(i))?;
+          let [a, b] = [diagnostics.subdiagonal()[0], diagnostics.subdiagonal()[1]];
+          spectrum.extend(inner);
+          spectrum.push(Eigenvalue::Real(H[3][3]));
+          let diagnostics = EigenDiagnostics::new(i + diagnostics.iterations, &[a, b, c]);
+          return Ok((spectrum.sorted(), diagnostics));
+        }
+
+        // The deflation checks also run once after the last iteration
+        if i == options.max_iterations {
+          break;
+        }
+
+        let (s, t) = shifts(&H, options.is_exceptional(i));
+        francis_step(&mut H, None, s, t);
+      }
+
+      Err(LinalgError::NoConvergence {
+        iterations: options.max_iterations,
+      })
+    }
 
-    let M = A * A - s * A + t * DMat3::IDENTITY;
-    let (Z, _) = qr3(M);
+    /// Computes the eigenvalues of a dense 3x3 matrix with the QR algorithm, along with diagnostics of the run
+    // The matrix is first normalized, see normalization, and the eigenvalues and subdiagonal magnitudes are
+    // scaled back afterwards
+    pub(crate) fn eigvals3(
+      A: $Mat3,
+      options: &EigenOptions,
+    ) -> Result<(Spectrum<$T, 3>, EigenDiagnostics<3>), LinalgError> {
+      if !A.is_finite() {
+        return Err(LinalgError::NonFiniteInput);
+      }
+      let scale = normalization(A.norm_max());
+      let (spectrum, diagnostics) = eigvals3_hessenberg(
+        hessenberg3_normalized(A / scale).1.to_cols_array_2d(),
+        options,
+      )?;
+      Ok((
+        spectrum.map(|x| x * scale),
+        diagnostics.scaled(scale.to_f64()),
+      ))
+    }
 
-    A = Z.transpose() * A * Z;
-  }
+    /// Computes the eigenvalues of a dense 4x4 matrix with the QR algorithm, along with diagnostics of the run
+    pub(crate) fn eigvals4(
+      A: $Mat4,
+      options: &EigenOptions,
+    ) -> Result<(Spectrum<$T, 4>, EigenDiagnostics<4>), LinalgError> {
+      if !A.is_finite() {
+        return Err(LinalgError::NonFiniteInput);
+      }
+      let scale = normalization(A.norm_max());
+      let (spectrum, diagnostics) = eigvals4_hessenberg(
+        hessenberg4_normalized(A / scale).1.to_cols_array_2d(),
+        options,
+      )?;
+      Ok((
+        spectrum.map(|x| x * scale),
+        diagnostics.scaled(scale.to_f64()),
+      ))
+    }
 
-  unreachable!();
-}
+    /// Computes the standardized real Schur form of a dense 2x2 matrix, returning (Z, T) with A = Z T Zᵀ
+    // Follows LAPACK's dlanv2: T is upper triangular if the eigenvalues are real, and otherwise has
+    // equal diagonal entries and off-diagonal entries of opposite signs
+    pub(crate) fn schur2(A: $Mat2) -> ($Mat2, $Mat2) {
+      let [[mut a, mut c], [mut b, mut d]] = A.to_cols_array_2d();
+      let (mut cs, mut sn);
+
+      if c == 0.0 {
+        cs = 1.0;
+        sn = 0.0;
+      } else if b == 0.0 {
+        // Swap the rows and columns
+        cs = 0.0;
+        sn = 1.0;
+        std::mem::swap(&mut a, &mut d);
+        b = -c;
+        c = 0.0;
+      } else if a == d && b.signum() != c.signum() {
+        cs = 1.0;
+        sn = 0.0;
+      } else {
+        let temp = a - d;
+        let p = 0.5 * temp;
+        let bcmax = b.abs().max(c.abs());
+        let bcmis = b.abs().min(c.abs()) * b.signum() * c.signum();
+        let scale = p.abs().max(bcmax);
+        let z = p / scale * p + bcmax / scale * bcmis;
+
+        if z >= 4.0 * $T::EPSILON {
+          // Real eigenvalues
+          let z = p + (scale.sqrt() * z.sqrt()).copysign(p);
+          a = d + z;
+          d -= bcmax / z * bcmis;
+          let tau = c.hypot(z);
+          cs = z / tau;
+          sn = c / tau;
+          b -= c;
+          c = 0.0;
+        } else {
+          // Complex or almost equal real eigenvalues, so make the diagonal entries equal
+          let sigma = b + c;
+          let tau = sigma.hypot(temp);
+          cs = (0.5 * (1.0 + sigma.abs() / tau)).sqrt();
+          sn = -(p / (tau * cs)) * sigma.signum();
+
+          let (aa, bb) = (a * cs + b * sn, -a * sn + b * cs);
+          let (cc, dd) = (c * cs + d * sn, -c * sn + d * cs);
+          b = bb * cs + dd * sn;
+          c = -aa * sn + cc * cs;
+
+          let temp = 0.5 * (aa * cs + cc * sn + (-bb * sn + dd * cs));
+          a = temp;
+          d = temp;
+
+          if c != 0.0 {
+            if b == 0.0 {
+              b = -c;
+              c = 0.0;
+              (cs, sn) = (-sn, cs);
+            } else if b.signum() == c.signum() {
+              // Real eigenvalues, so reduce to upper triangular form
+              let sab = b.abs().sqrt();
+              let sac = c.abs().sqrt();
+              let p = (sab * sac).copysign(c);
+              let tau = (b + c).abs().sqrt().recip();
+              a = temp + p;
+              d = temp - p;
+              b -= c;
+              c = 0.0;
+              let (cs1, sn1) = (sab * tau, sac * tau);
+              (cs, sn) = (cs * cs1 - sn * sn1, cs * sn1 + sn * cs1);
+            }
+          }
+        }
+      }
+
+      let Z = $Mat2::from_cols($vec2(cs, sn), $vec2(-sn, cs));
+      let T = $Mat2::from_cols($vec2(a, c), $vec2(b, d));
+      (Z, T)
+    }
+
+    /// Computes the standardized real Schur form of a 3x3 hessenberg matrix, returning (Z, T) with A = Z T Zᵀ
+    // This is the QR algorithm of eigvals3_hessenberg, keeping track of the similarity transforms
+    pub(crate) fn schur3_hessenberg(
+      A: $Mat3,
+      options: &EigenOptions,
+    ) -> Result<($Mat3, $Mat3), LinalgError> {
+      // The Francis steps work on the arrays in place, which are read back into glam types on deflation
+      let (mut H, mut Q) = (A.to_cols_array_2d(), $Mat3::IDENTITY.to_cols_array_2d());
+
+      for i in 0..=options.max_iterations {
+        // If some subdiagonal element is small enough, deflate and standardize the remaining 2x2 block
+        if options.is_negligible(H[0][1], H[0][0], H[1][1]) {
+          let (A, Z) = ($Mat3::from_cols_array_2d(&H), $Mat3::from_cols_array_2d(&Q));
+          let (G, T) = schur2($Mat2::from_mat3_minor(A, 0, 0));
+          let G = embed_lower2_3(G);
+          let mut A = G.transpose() * A * G;
+
+          A.x_axis = $vec3(A.x_axis.x, 0.0, 0.0);
+          A.y_axis = $vec3(A.y_axis.x, T.x_axis.x, T.x_axis.y);
+          A.z_axis = $vec3(A.z_axis.x, T.y_axis.x, T.y_axis.y);
+          return Ok((Z * G, A));
+        }
+        if options.is_negligible(H[1][2], H[1][1], H[2][2]) {
+          let (A, Z) = ($Mat3::from_cols_array_2d(&H), $Mat3::from_cols_array_2d(&Q));
+          let (G, T) = schur2($Mat2::from_mat3(A));
+          let G = $Mat3::from_mat2(G);
+          let mut A = G.transpose() * A * G;
+
+          A.x_axis = T.x_axis.extend(0.0);
+          A.y_axis = T.y_axis.extend(0.0);
+          return Ok((Z * G, A));
+        }
+
+        // The deflation checks also run once after the last iteration
+        if i == options.max_iterations {
+          break;
+        }
+
+        let (s, t) = shifts(&H, options.is_exceptional(i));
+        francis_step(&mut H, Some(&mut Q), s, t);
+      }
+
+      Err(LinalgError::NoConvergence {
+        iterations: options.max_iterations,
+      })
+    }
+
+    /// Computes the standardized real Schur form of a 4x4 hessenberg matrix, returning (Z, T) with A = Z T Zᵀ
+    pub(crate) fn schur4_hessenberg(
+      A: $Mat4,
+      options: &EigenOptions,
+    ) -> Result<($Mat4, $Mat4), LinalgError> {
+      let (mut H, mut Q) = (A.to_cols_array_2d(), $Mat4::IDENTITY.to_cols_array_2d());
+
+      for i in 0..=options.max_iterations {
+        // If some subdiagonal element is small enough, deflate and find the Schur forms of the remaining blocks
+        if options.is_negligible(H[0][1], H[0][0], H[1][1]) {
+          let (A, Z) = ($Mat4::from_cols_array_2d(&H), $Mat4::from_cols_array_2d(&Q));
+          let (G, T) =
+            schur3_hessenberg($Mat3::from_mat4_minor(A, 0, 0), options).map_err(|e| e.after(i))?;
+          let G = embed_lower3_4(G);
+          let mut A = G.transpose() * A * G;
+
+          A.x_axis = $vec4(A.x_axis.x, 0.0, 0.0, 0.0);
+          A.y_axis = $vec4(A.y_axis.x, T.x_axis.x, T.x_axis.y, T.x_axis.z);
+          A.z_axis = $vec4(A.z_axis.x, T.y_axis.x, T.y_axis.y, T.y_axis.z);
+          A.w_axis = $vec4(A.w_axis.x, T.z_axis.x, T.z_axis.y, T.z_axis.z);
+          return Ok((Z * G, A));
+        }
+        if options.is_negligible(H[1][2], H[1][1], H[2][2]) {
+          let (A, Z) = ($Mat4::from_cols_array_2d(&H), $Mat4::from_cols_array_2d(&Q));
+          let (G1, T1) = schur2($Mat2::from_cols(A.x_axis.xy(), A.y_axis.xy()));
+          let (G2, T2) = schur2($Mat2::from_cols(A.z_axis.zw(), A.w_axis.zw()));
+          let G = block_diag2(G1, G2);
+          let mut A = G.transpose() * A * G;
+
+          A.x_axis = $vec4(T1.x_axis.x, T1.x_axis.y, 0.0, 0.0);
+          A.y_axis = $vec4(T1.y_axis.x, T1.y_axis.y, 0.0, 0.0);
+          A.z_axis = $vec4(A.z_axis.x, A.z_axis.y, T2.x_axis.x, T2.x_axis.y);
+          A.w_axis = $vec4(A.w_axis.x, A.w_axis.y, T2.y_axis.x, T2.y_axis.y);
+          return Ok((Z * G, A));
+        }
+        if options.is_negligible(H[2][3], H[2][2], H[3][3]) {
+          let (A, Z) = ($Mat4::from_cols_array_2d(&H), $Mat4::from_cols_array_2d(&Q));
+          let (G, T) = schur3_hessenberg($Mat3::from_mat4(A), options).map_err(|e| e.after(i))?;
+          let G = $Mat4::from_mat3(G);
+          let mut A = G.transpose() * A * G;
+
+          A.x_axis = T.x_axis.extend(0.0);
+          A.y_axis = T.y_axis.extend(0.0);
+          A.z_axis = T.z_axis.extend(0.0);
+          return Ok((Z * G, A));
+        }
+
+        // The deflation checks also run once after the last iteration
+        if i == options.max_iterations {
+          break;
+        }
+
+        let (s, t) = shifts(&H, options.is_exceptional(i));
+        francis_step(&mut H, Some(&mut Q), s, t);
+      }
+
+      Err(LinalgError::NoConvergence {
+        iterations: options.max_iterations,
+      })
+    }
 
-/// Computes the eigenvalues of a 4x4 hessenberg matrix
-pub(crate) fn eigvals4_hessenberg(mut A: DMat4) -> [DVec2; 4] {
-  const CUTOFF: f64 = 1e-14;
+    /// Computes the eigenvalues and unit eigenvectors of a matrix in standardized real Schur form
+    // The matrix is given as an array of columns, and each eigenvector is found by back substitution
+    // through the blocks above its own. The eigenpairs are returned in ascending order
+    pub(crate) fn schur_eig<const N: usize>(
+      T: [[$T; N]; N],
+    ) -> ([$Complex; N], [[$Complex; N]; N]) {
+      // Singular diagonal blocks are perturbed by this amount, as in LAPACK's dtrevc
+      let norm = norm_max(&T);
+      let smin = ($T::EPSILON * norm).max($T::MIN_POSITIVE);
+
+      let mut eigvals = [$Complex::ZERO; N];
+      let mut eigvecs = [[$Complex::ZERO; N]; N];
+
+      let mut k = 0;
+      while k < N {
+        let mut y = [$Complex::ZERO; N];
+
+        if k + 1 < N && T[k][k + 1] != 0.0 {
+          // A 2x2 block [p q; r p] with q * r < 0, whose eigenvector for p + iω is (q, iω)
+          let omega = T[k][k + 1].abs().sqrt() * T[k + 1][k].abs().sqrt();
+          let lambda = $Complex::new(T[k][k], omega);
+          y[k] = complex(T[k + 1][k]);
+          y[k + 1] = $Complex::new(0.0, omega);
+          back_substitute(&T, &mut y, k, lambda, smin);
+          normalize(&mut y);
+
+          eigvals[k] = lambda;
+          eigvals[k + 1] = lambda.conj();
+          eigvecs[k] = y;
+          eigvecs[k + 1] = y.map($Complex::conj);
+          k += 2;
+        } else {
+          let lambda = complex(T[k][k]);
+          y[k] = complex(1.0);
+          back_substitute(&T, &mut y, k, lambda, smin);
+          normalize(&mut y);
+
+          eigvals[k] = lambda;
+          eigvecs[k] = y;
+          k += 1;
+        }
+      }
+
+      let perm = EigenOrder::Ascending.argsort_complex(&eigvals);
+      (perm.map(|i| eigvals[i]), perm.map(|i| eigvecs[i]))
+    }
 
-  // We shouldn't have more than a couple dozen iterations
-  for _ in 0..100 {
-    // If some subdiagonal element is small enough, deflate
-    if A.x_axis.y.abs() <= CUTOFF * (A.x_axis.x.abs() + A.y_axis.y.abs()) {
-      let [a, b, c] = eigvals3_hessenberg(DMat3::from_mat4_minor(A, 0, 0));
-      return [complex(A.x_axis.x), a, b, c];
+    /// Solves (T - λI) y = 0 for the entries of y above row k, given the entries from row k onwards
+    fn back_substitute<const N: usize>(
+      T: &[[$T; N]; N],
+      y: &mut [$Complex; N],
+      k: usize,
+      lambda: $Complex,
+      smin: $T,
+    ) {
+      let guard = |x: $Complex| if x.abs() < smin { complex(smin) } else { x };
+      let rhs = |y: &[$Complex; N], i: usize, from: usize| -> $Complex {
+        -(from..N).map(|j| T[j][i] * y[j]).sum::<$Complex>()
+      };
+
+      // Rows below i have been solved for
+      let mut i = k;
+      while i > 0 {
+        if i >= 2 && T[i - 2][i - 1] != 0.0 {
+          // Rows i - 2 and i - 1 form a 2x2 block, so solve the 2x2 system by Cramer's rule
+          let a = complex(T[i - 2][i - 2]) - lambda;
+          let b = complex(T[i - 1][i - 2]);
+          let c = complex(T[i - 2][i - 1]);
+          let d = complex(T[i - 1][i - 1]) - lambda;
+          let (r0, r1) = (rhs(y, i - 2, i), rhs(y, i - 1, i));
+
+          let det = guard(a * d - b * c);
+          y[i - 2] = (r0 * d - b * r1) / det;
+          y[i - 1] = (a * r1 - c * r0) / det;
+          i -= 2;
+        } else {
+          let d = guard(complex(T[i - 1][i - 1]) - lambda);
+          y[i - 1] = rhs(y, i - 1, i) / d;
+          i -= 1;
+        }
+      }
     }
-    if A.y_axis.z.abs() <= CUTOFF * (A.y_axis.y.abs() + A.z_axis.z.abs()) {
-      let [a, b] = eigvals2(DMat2::from_cols(A.x_axis.xy(), A.y_axis.xy()));
-      let [c, d] = eigvals2(DMat2::from_cols(A.z_axis.zw(), A.w_axis.zw()));
-      return [a, b, c, d];
+
+    fn normalize<const N: usize>(y: &mut [$Complex; N]) {
+      let norm = y.iter().map(|x| x.norm_sqr()).sum::<$T>().sqrt();
+      y.iter_mut().for_each(|x| *x /= norm);
     }
-    if A.z_axis.w.abs() <= CUTOFF * (A.z_axis.z.abs() + A.w_axis.w.abs()) {
-      let [a, b, c] = eigvals3_hessenberg(DMat3::from_mat4(A));
-      return [a, b, c, complex(A.w_axis.w)];
+
+    /// Computes the eigenvalues and eigenvectors of a dense 2x2 matrix
+    pub(crate) fn eig2(A: $Mat2) -> ([$Complex; 2], $Mat2, $Mat2) {
+      if !A.is_finite() {
+        return ([complex($T::NAN); 2], $Mat2::NAN, $Mat2::NAN);
+      }
+      let (Z, T) = schur2(A);
+      let (eigvals, Y) = schur_eig(T.to_cols_array_2d());
+      let re = $Mat2::from_cols_array_2d(&Y.map(|y| y.map(|x| x.re)));
+      let im = $Mat2::from_cols_array_2d(&Y.map(|y| y.map(|x| x.im)));
+      (eigvals, Z * re, Z * im)
     }
 
-    // Double shift QR step
-    let s = A.y_axis.z + A.z_axis.w;
-    let t = A.y_axis.z * A.z_axis.w - A.y_axis.w * A.z_axis.z;
+    /// Computes the standardized real Schur form of a dense 3x3 matrix, returning (Z, T) with A = Z T Zᵀ
+    // The matrix is first normalized, see normalization
+    pub(crate) fn schur3(A: $Mat3) -> Result<($Mat3, $Mat3), LinalgError> {
+      if !A.is_finite() {
+        return Err(LinalgError::NonFiniteInput);
+      }
+      let scale = normalization(A.norm_max());
+      let (Q, H) = hessenberg3_normalized(A / scale);
+      let (Z, T) = schur3_hessenberg(H, &EigenOptions::default())?;
+      Ok((Q * Z, T * scale))
+    }
 
-    let M = A * A - s * A + t * DMat4::IDENTITY;
-    let (Z, _) = qr4(M);
+    /// Computes the eigenvalues and eigenvectors of a dense 3x3 matrix
+    pub(crate) fn eig3(A: $Mat3) -> Result<([$Complex; 3], $Mat3, $Mat3), LinalgError> {
+      if !A.is_finite() {
+        return Ok(([complex($T::NAN); 3], $Mat3::NAN, $Mat3::NAN));
+      }
+      let (Z, T) = schur3(A)?;
+      let (eigvals, Y) = schur_eig(T.to_cols_array_2d());
+      let re = $Mat3::from_cols_array_2d(&Y.map(|y| y.map(|x| x.re)));
+      let im = $Mat3::from_cols_array_2d(&Y.map(|y| y.map(|x| x.im)));
+      Ok((eigvals, Z * re, Z * im))
+    }
 
-    A = Z.transpose() * A * Z;
-  }
+    /// Computes the standardized real Schur form of a dense 4x4 matrix, returning (Z, T) with A = Z T Zᵀ
+    pub(crate) fn schur4(A: $Mat4) -> Result<($Mat4, $Mat4), LinalgError> {
+      if !A.is_finite() {
+        return Err(LinalgError::NonFiniteInput);
+      }
+      let scale = normalization(A.norm_max());
+      let (Q, H) = hessenberg4_normalized(A / scale);
+      let (Z, T) = schur4_hessenberg(H, &EigenOptions::default())?;
+      Ok((Q * Z, T * scale))
+    }
 
-  unreachable!();
+    /// Computes the eigenvalues and eigenvectors of a dense 4x4 matrix
+    pub(crate) fn eig4(A: $Mat4) -> Result<([$Complex; 4], $Mat4, $Mat4), LinalgError> {
+      if !A.is_finite() {
+        return Ok(([complex($T::NAN); 4], $Mat4::NAN, $Mat4::NAN));
+      }
+      let (Z, T) = schur4(A)?;
+      let (eigvals, Y) = schur_eig(T.to_cols_array_2d());
+      let re = $Mat4::from_cols_array_2d(&Y.map(|y| y.map(|x| x.re)));
+      let im = $Mat4::from_cols_array_2d(&Y.map(|y| y.map(|x| x.im)));
+      Ok((eigvals, Z * re, Z * im))
+    }
+  };
 }
+
+pub(crate) use impl_eigen;
+
+impl_eigen!(f64, Complex64, DMat2, DMat3, DMat4, dvec2, dvec3, dvec4);
@@ -1,53 +1,354 @@
-use crate::{DMatExt, EigenDecomposition};
-
-use glam::{DMat2, DMat3, DMat4};
-
-/// Computes the eigenvalues of a dense, symmetric 2x2 matrix
-// Direct solving of characteristic polynomial
-pub(crate) fn eigvals2_symmetric(A: DMat2) -> [f64; 2] {
-  let t = A.trace();
-  let d = A.determinant();
-  let center = 0.5 * t;
-  let gap = (0.25 * t * t - d).sqrt();
-  [center - gap, center + gap]
+use crate::utils::*;
+use crate::{DMatExt, EigenOrder, LinalgError};
+
+use glam::{dvec2, DMat2, DMat3, DMat4, DVec3, Vec3Swizzles, Vec4Swizzles};
+
+/// Computes the eigenvalues of a symmetric tridiagonal matrix with the implicit QL algorithm
+// d holds the diagonal and e the subdiagonal, where e[i] couples rows i and i + 1 and e[N - 1] is unused
+// Follows tqli from Numerical Recipes with its standard QL shift: the eigenvalue of the 2x2 block at rows l and
+// l + 1, where the active block deflates, that lies closer to d[l]
+pub(crate) fn tridiagonal_ql<T: Real, const N: usize>(
+  mut d: [T; N],
+  mut e: [T; N],
+) -> Result<[T; N], LinalgError> {
+  let mut iterations = 0;
+
+  'outer: for l in 0..N {
+    // We shouldn't have more than a handful of iterations per eigenvalue
+    'iterate: for _ in 0..100 {
+      // Split the matrix at the first negligible subdiagonal element
+      let m = (l..N - 1)
+        .find(|&m| e[m].abs() <= T::EPSILON * (d[m].abs() + d[m + 1].abs()))
+        .unwrap_or(N - 1);
+      if m == l {
+        continue 'outer;
+      }
+      iterations += 1;
+
+      let g = (d[l + 1] - d[l]) / (T::from_f64(2.0) * e[l]);
+      let mut g = d[m] - d[l] + e[l] / (g + g.hypot(T::ONE).copysign(g));
+      let (mut s, mut c, mut p) = (T::ONE, T::ONE, T::ZERO);
+
+      // Chase the bulge from the bottom of the block back up to row l
+      for i in (l..m).rev() {
+        let f = s * e[i];
+        let b = c * e[i];
+        let r = f.hypot(g);
+        e[i + 1] = r;
+
+        if r == T::ZERO {
+          // Recover from underflow by restarting the iteration
+          d[i + 1] -= p;
+          e[m] = T::ZERO;
+          continue 'iterate;
+        }
+
+        s = f / r;
+        c = g / r;
+        let h = d[i + 1] - p;
+        let r = (d[i] - h) * s + T::from_f64(2.0) * c * b;
+        p = s * r;
+        d[i + 1] = h + p;
+        g = c * r - b;
+      }
+
+      d[l] -= p;
+      e[l] = g;
+      e[m] = T::ZERO;
+    }
+
+    return Err(LinalgError::NoConvergence { iterations });
+  }
+
+  Ok(d)
 }
 
-/// Computes the eigenvalues of a dense, symmetric 3x3 matrix
-// TODO: This algorithm is much quicker than the QR algorithm for symmetric 3x3 matrices, but has lower precision
-// Uses the algorithm given in https://en.wikipedia.org/wiki/Eigenvalue_algorithm#Symmetric_3%C3%973_matrices (Nov 20, 2024)
-pub(crate) fn eigvals3_symmetric(A: DMat3) -> [f64; 3] {
-  use std::f64::consts::PI;
-
-  let p1 = A.y_axis.x.powi(2) + A.z_axis.x.powi(2) + A.z_axis.y.powi(2);
-
-  if p1 == 0.0 {
-    // A is diagonal
-    [A.x_axis.x, A.y_axis.y, A.z_axis.z]
-  } else {
-    let tr = A.trace();
-    let q = tr / 3.0;
-    let p2 =
-      (A.x_axis.x - q).powi(2) + (A.y_axis.y - q).powi(2) + (A.z_axis.z - q).powi(2) + 2.0 * p1;
-    let p = (p2 / 6.0).sqrt();
-    let B = (A - q * DMat3::IDENTITY) / p;
-    let r = B.determinant() / 2.0;
-
-    // In exact arithmetic for a symmetric matrix -1 <= r <= 1
-    // but computation error can leave it slightly outside this range.
-    let phi = r.clamp(-1.0, 1.0).acos() / 3.0;
-
-    // the eigenvalues satisfy c <= b <= a
-    let a = q + 2.0 * p * phi.cos();
-    let c = q + 2.0 * p * (phi + (2.0 * PI / 3.0)).cos();
-    let b = tr - a - c;
-
-    [a, b, c]
+/// Computes the eigenvalues and eigenvectors of a symmetric matrix with the cyclic Jacobi method
+// The matrices are given as arrays of columns. Each rotation zeroes one off-diagonal pair, and
+// rotations are skipped once the pair is negligible relative to its diagonal entries
+pub(crate) fn jacobi<T: Real, const N: usize>(mut A: [[T; N]; N]) -> ([T; N], [[T; N]; N]) {
+  let mut V = [[T::ZERO; N]; N];
+  (0..N).for_each(|i| V[i][i] = T::ONE);
+
+  // Jacobi converges quadratically, so this limit is never reached in practice
+  for _ in 0..64 {
+    let mut rotated = false;
+
+    for p in 0..N {
+      for q in p + 1..N {
+        let apq = A[q][p];
+        if apq.abs() <= T::EPSILON * A[p][p].abs().sqrt() * A[q][q].abs().sqrt() || apq == T::ZERO {
+          continue;
+        }
+        rotated = true;
+
+        let theta = (A[q][q] - A[p][p]) / (T::from_f64(2.0) * apq);
+        let t = theta.signum() / (theta.abs() + theta.hypot(T::ONE));
+        let c = (t * t + T::ONE).sqrt().recip();
+        let s = t * c;
+
+        // A <- Jᵀ A J and V <- V J, where J rotates the p and q axes
+        rotate_cols(&mut A, p, q, c, s);
+        for col in A.iter_mut() {
+          let (x, y) = (col[p], col[q]);
+          col[p] = c * x - s * y;
+          col[q] = s * x + c * y;
+        }
+        A[q][p] = T::ZERO;
+        A[p][q] = T::ZERO;
+
+        rotate_cols(&mut V, p, q, c, s);
+      }
+    }
+
+    if !rotated {
+      break;
+    }
   }
+
+  (std::array::from_fn(|i| A[i][i]), V)
 }
 
-/// Computes the eigenvalues of a dense, symmetric 4x4 matrix
-// TODO: Implement an algorithm that takes advantage of the symmetric structure
-pub(crate) fn eigvals4_symmetric(A: DMat4) -> [f64; 4] {
-  let [a, b, c, d] = A.eigvals();
-  [a.x, b.x, c.x, d.x]
+// The algorithms on glam types, generated for the f64 types here and for the f32 types in the single module
+macro_rules! impl_eigen_symmetric {
+  ($T:ident, $Mat2:ident, $Mat3:ident, $Mat4:ident, $Vec3:ident, $vec2:ident) => {
+    /// Computes the eigenvalues of a dense, symmetric 2x2 matrix, in ascending order
+    // Direct solving of characteristic polynomial, with the discriminant formed without cancellation
+    // The off-diagonal entry is taken as the geometric mean of both, as in the determinant
+    // The root of larger magnitude is |center| + gap, and the other is the determinant divided by it, so that
+    // a small eigenvalue does not cancel to zero
+    pub(crate) fn eigvals2_symmetric(A: $Mat2) -> [$T; 2] {
+      let (a, c) = (A.x_axis.x, A.y_axis.y);
+      let center = 0.5 * (a + c);
+      let off = A.x_axis.y.abs().sqrt() * A.y_axis.x.abs().sqrt();
+      let gap = (0.5 * (a - c)).hypot(off);
+      let large = center + gap.copysign(center);
+      // Dividing first keeps the determinant from overflowing, as |large| bounds |a|, |c| and off
+      let small = if large == 0.0 {
+        0.0
+      } else {
+        (a / large) * c - (off / large) * off
+      };
+      [large.min(small), large.max(small)]
+    }
+
+    /// Computes the eigenvalues of a dense, symmetric 3x3 matrix, in ascending order
+    // This is a hybrid method: the closed form of eigvals3_symmetric_trig is accurate to a few ulps when the
+    // eigenvalues are well separated, and otherwise the matrix is reduced to tridiagonal form, which is then
+    // solved with the implicit QL algorithm
+    // Either way the matrix is first normalized, see normalization
+    pub(crate) fn eigvals3_symmetric(A: $Mat3) -> Result<[$T; 3], LinalgError> {
+      let scale = normalization(A.norm_max());
+      Ok(eigvals3_symmetric_normalized(A / scale)?.map(|x| x * scale))
+    }
+
+    // The hybrid method of eigvals3_symmetric, for matrices the caller has already normalized
+    fn eigvals3_symmetric_normalized(A: $Mat3) -> Result<[$T; 3], LinalgError> {
+      let [a, b, c] = eigvals3_symmetric_trig(A);
+
+      // The closed form loses precision as the relative gap between the eigenvalues shrinks
+      if (a - b).min(b - c) >= 1e-2 * a.abs().max(c.abs()) {
+        return Ok([c, b, a]);
+      }
+
+      let (d, e) = tridiagonal3(A);
+      let mut eigvals = tridiagonal_ql(d, e)?;
+      eigvals.sort_by($T::total_cmp);
+      Ok(eigvals)
+    }
+
+    /// Computes the diagonal and subdiagonal of the tridiagonal form of a symmetric 3x3 matrix
+    fn tridiagonal3(A: $Mat3) -> ([$T; 3], [$T; 3]) {
+      let mut v = A.x_axis.yz();
+      let alpha = -v.length() * v.x.signum();
+      v.x -= alpha;
+      v = v.normalize_or_zero();
+
+      let B = $Mat2::from_mat3_minor(A, 0, 0);
+      let p = 2.0 * (B * v);
+      let w = p - p.dot(v) * v;
+      let B = B - outer_product2(v, w) - outer_product2(w, v);
+
+      (
+        [A.x_axis.x, B.x_axis.x, B.y_axis.y],
+        [alpha, B.x_axis.y, 0.0],
+      )
+    }
+
+    /// Computes the eigenvalues of a dense, symmetric 3x3 matrix in closed form
+    // This algorithm is much quicker than the QL algorithm, but loses precision for nearly repeated eigenvalues
+    // The eigenvalues satisfy c <= b <= a, unless A is diagonal
+    // Uses the algorithm given in https://en.wikipedia.org/wiki/Eigenvalue_algorithm#Symmetric_3%C3%973_matrices (Nov 20, 2024)
+    pub(crate) fn eigvals3_symmetric_trig(A: $Mat3) -> [$T; 3] {
+      use std::$T::consts::PI;
+
+      let p1 = A.y_axis.x.powi(2) + A.z_axis.x.powi(2) + A.z_axis.y.powi(2);
+
+      if p1 == 0.0 {
+        // A is diagonal
+        [A.x_axis.x, A.y_axis.y, A.z_axis.z]
+      } else {
+        let tr = A.trace();
+        let q = tr / 3.0;
+        let p2 =
+          (A.x_axis.x - q).powi(2) + (A.y_axis.y - q).powi(2) + (A.z_axis.z - q).powi(2) + 2.0 * p1;
+        let p = (p2 / 6.0).sqrt();
+        let B = (A - q * $Mat3::IDENTITY) / p;
+        let r = B.determinant() / 2.0;
+
+        // In exact arithmetic for a symmetric matrix -1 <= r <= 1
+        // but computation error can leave it slightly outside this range.
+        let phi = r.clamp(-1.0, 1.0).acos() / 3.0;
+
+        // the eigenvalues satisfy c <= b <= a
+        let a = q + 2.0 * p * phi.cos();
+        let c = q + 2.0 * p * (phi + (2.0 * PI / 3.0)).cos();
+        let b = tr - a - c;
+
+        [a, b, c]
+      }
+    }
+
+    /// Computes the eigenvalues of a dense, symmetric 4x4 matrix, in ascending order
+    // The matrix is reduced to tridiagonal form, which is then solved with the implicit QL algorithm
+    // The matrix is first normalized, see normalization
+    pub(crate) fn eigvals4_symmetric(A: $Mat4) -> Result<[$T; 4], LinalgError> {
+      let scale = normalization(A.norm_max());
+      let (d, e) = tridiagonal4(A / scale);
+      let mut eigvals = tridiagonal_ql(d, e)?.map(|x| x * scale);
+      eigvals.sort_by($T::total_cmp);
+      Ok(eigvals)
+    }
+
+    /// Computes the diagonal and subdiagonal of the tridiagonal form of a symmetric 4x4 matrix
+    // This is hessenberg4 specialized to symmetric matrices, where each reflector P = I - 2vvᵀ is applied
+    // as a symmetric rank two update PBP = B - vwᵀ - wvᵀ of the trailing block
+    fn tridiagonal4(A: $Mat4) -> ([$T; 4], [$T; 4]) {
+      let mut v = A.x_axis.yzw();
+      let alpha = -v.length() * v.x.signum();
+      v.x -= alpha;
+      v = v.normalize_or_zero();
+
+      let B = $Mat3::from_mat4_minor(A, 0, 0);
+      let p = 2.0 * (B * v);
+      let w = p - p.dot(v) * v;
+      let B = B - outer_product3(v, w) - outer_product3(w, v);
+
+      let mut u = B.x_axis.yz();
+      let beta = -u.length() * u.x.signum();
+      u.x -= beta;
+      u = u.normalize_or_zero();
+
+      let C = $Mat2::from_mat3_minor(B, 0, 0);
+      let p = 2.0 * (C * u);
+      let w = p - p.dot(u) * u;
+      let C = C - outer_product2(u, w) - outer_product2(w, u);
+
+      (
+        [A.x_axis.x, B.x_axis.x, C.x_axis.x, C.y_axis.y],
+        [alpha, beta, C.x_axis.y, 0.0],
+      )
+    }
+
+    /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 2x2 matrix
+    // A single Jacobi rotation diagonalizes the matrix, with the eigenvalues in the order of eigvals2_symmetric
+    pub(crate) fn eig2_symmetric(A: $Mat2) -> ([$T; 2], $Mat2) {
+      if !A.is_finite() {
+        return ([$T::NAN; 2], $Mat2::NAN);
+      }
+      let eigvals = eigvals2_symmetric(A);
+
+      // The rotation by theta maps the first axis onto the eigenvector of the larger eigenvalue
+      let theta = 0.5 * (2.0 * A.y_axis.x).atan2(A.x_axis.x - A.y_axis.y);
+      let (s, c) = theta.sin_cos();
+
+      (eigvals, $Mat2::from_cols($vec2(-s, c), $vec2(c, s)))
+    }
+
+    /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 3x3 matrix
+    // Uses the algorithm given in https://www.geometrictools.com/Documentation/RobustEigenSymmetric3x3.pdf
+    // The eigenvector of the most isolated eigenvalue is found from the cross products of the rows of A - λI,
+    // after which the remaining two are found from a 2x2 problem on its orthogonal complement
+    // The matrix is first normalized, see normalization
+    pub(crate) fn eig3_symmetric(A: $Mat3) -> Result<([$T; 3], $Mat3), LinalgError> {
+      if !A.is_finite() {
+        return Ok(([$T::NAN; 3], $Mat3::NAN));
+      }
+      let scale = normalization(A.norm_max());
+      let A = A / scale;
+      let eigvals = eigvals3_symmetric_normalized(A)?;
+      let [a, b, c] = eigvals;
+
+      let V = if c - b >= b - a {
+        let w = eigvec3_symmetric(A, c);
+        let v = eigvec3_symmetric_complement(A, w, b);
+        $Mat3::from_cols(v.cross(w), v, w)
+      } else {
+        let u = eigvec3_symmetric(A, a);
+        let v = eigvec3_symmetric_complement(A, u, b);
+        $Mat3::from_cols(u, v, u.cross(v))
+      };
+      Ok((eigvals.map(|x| x * scale), V))
+    }
+
+    /// Computes an eigenvector of a symmetric 3x3 matrix for a simple eigenvalue
+    fn eigvec3_symmetric(A: $Mat3, lambda: $T) -> $Vec3 {
+      // The rows of a symmetric matrix are its columns
+      let M = A - lambda * $Mat3::IDENTITY;
+      let candidates = [
+        M.x_axis.cross(M.y_axis),
+        M.x_axis.cross(M.z_axis),
+        M.y_axis.cross(M.z_axis),
+      ];
+
+      let v = candidates
+        .into_iter()
+        .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+        .unwrap();
+
+      // If every cross product vanishes, then A = λI and every vector is an eigenvector
+      v.try_normalize().unwrap_or($Vec3::X)
+    }
+
+    /// Computes an eigenvector of a symmetric 3x3 matrix that is orthogonal to a known unit eigenvector w
+    fn eigvec3_symmetric_complement(A: $Mat3, w: $Vec3, lambda: $T) -> $Vec3 {
+      let (u, v) = w.any_orthonormal_pair();
+
+      // Restrict A - λI to the orthogonal complement of w, and find the null vector of the 2x2 result
+      let Au = A * u - lambda * u;
+      let Av = A * v - lambda * v;
+      let (m00, m01, m11) = (u.dot(Au), u.dot(Av), v.dot(Av));
+
+      let (p, q) = if m00.abs() >= m11.abs() {
+        (m01, m00)
+      } else {
+        (m11, m01)
+      };
+
+      // (p, -q) is orthogonal to the row (q, p) of largest magnitude, and is a null vector if one exists
+      let r = p.hypot(q);
+      if r > 0.0 {
+        (p / r) * u - (q / r) * v
+      } else {
+        u
+      }
+    }
+
+    /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 4x4 matrix, in ascending order
+    pub(crate) fn eig4_symmetric(A: $Mat4) -> ([$T; 4], $Mat4) {
+      if !A.is_finite() {
+        return ([$T::NAN; 4], $Mat4::NAN);
+      }
+      let (eigvals, V) = jacobi(A.to_cols_array_2d());
+      let perm = EigenOrder::Ascending.argsort(&eigvals);
+      (
+        perm.map(|i| eigvals[i]),
+        $Mat4::from_cols_array_2d(&perm.map(|i| V[i])),
+      )
+    }
+  };
 }
+
+pub(crate) use impl_eigen_symmetric;
+
+impl_eigen_symmetric!(f64, DMat2, DMat3, DMat4, DVec3, dvec2);
@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// The errors returned by the fallible decompositions
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinalgError {
+  /// The input matrix contains a NaN or infinite entry
+  NonFiniteInput,
+  /// An iterative algorithm failed to converge within its iteration limit
+  NoConvergence { iterations: usize },
+  /// The matrix has an eigenvalue on the closed negative real axis, where the principal logarithm and
+  /// square root are not defined
+  NegativeRealEigenvalue,
+}
+
+impl LinalgError {
+  // Accounts for the iterations of an outer loop when a nested iteration fails
+  pub(crate) fn after(self, iterations: usize) -> Self {
+    match self {
+      Self::NoConvergence { iterations: inner } => Self::NoConvergence {
+        iterations: inner + iterations,
+      },
+      err => err,
+    }
+  }
+}
+
+impl fmt::Display for LinalgError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::NonFiniteInput => write!(f, "the input matrix is not finite"),
+      Self::NoConvergence { iterations } => {
+        write!(f, "failed to converge after {iterations} iterations")
+      }
+      Self::NegativeRealEigenvalue => {
+        write!(
+          f,
+          "the matrix has an eigenvalue on the closed negative real axis"
+        )
+      }
+    }
+  }
+}
+
+impl std::error::Error for LinalgError {}
@@ -0,0 +1,337 @@
+use crate::lu::*;
+use crate::{DMatExt, EigenDecomposition, LinalgError};
+
+use glam::{DMat2, DMat3, DMat4, DVec3};
+
+use std::ops::{Add, Mul, Sub};
+
+// The operations the matrix functions need from the dense matrix types
+pub(crate) trait SquareMatrix:
+  DMatExt
+  + Copy
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Mul<f64, Output = Self>
+{
+  const IDENTITY: Self;
+
+  // Solves self X = B, assuming self is regular
+  fn solve(&self, B: Self) -> Self;
+
+  fn det(&self) -> f64;
+
+  fn is_finite(&self) -> bool;
+
+  // Fails if the matrix is not finite or has an eigenvalue on the closed negative real axis
+  fn check_principal(&self) -> Result<(), LinalgError>;
+}
+
+macro_rules! impl_square_matrix {
+  ($Mat:ty) => {
+    impl SquareMatrix for $Mat {
+      const IDENTITY: Self = <$Mat>::IDENTITY;
+
+      fn solve(&self, B: Self) -> Self {
+        let Lu { L, U, perm, .. } = lu(self.to_cols_array_2d());
+        let X = B.to_cols_array_2d().map(|b| lu_solve(&L, &U, &perm, b));
+        <$Mat>::from_cols_array_2d(&X)
+      }
+
+      fn det(&self) -> f64 {
+        self.determinant()
+      }
+
+      fn is_finite(&self) -> bool {
+        <$Mat>::is_finite(self)
+      }
+
+      fn check_principal(&self) -> Result<(), LinalgError> {
+        // Only the eigenvalues computed as real and non-positive are rejected, as a small positive eigenvalue
+        // may well be accurate, as for diagonal matrices
+        let on_axis = self
+          .try_eigvals()?
+          .iter()
+          .any(|lambda| lambda.im == 0.0 && lambda.re <= 0.0);
+
+        if on_axis {
+          return Err(LinalgError::NegativeRealEigenvalue);
+        }
+        Ok(())
+      }
+    }
+  };
+}
+
+impl_square_matrix!(DMat2);
+impl_square_matrix!(DMat3);
+impl_square_matrix!(DMat4);
+
+/// Computes the exponential of a dense 2x2 matrix in closed form
+// With μ = tr(A) / 2 and δ² = det(μ I - A), the Cayley-Hamilton theorem gives
+// exp(A) = e^μ (cosh(δ) I + sinh(δ) / δ (A - μ I)), where δ may be imaginary
+pub(crate) fn exp2(A: DMat2) -> DMat2 {
+  let mu = 0.5 * (A.x_axis.x + A.y_axis.y);
+  let B = A - mu * DMat2::IDENTITY;
+  let delta_sq = -B.determinant();
+  let delta = delta_sq.abs().sqrt();
+
+  if delta_sq > 0.0 && delta > 1.0 {
+    // For well separated real eigenvalues μ ± δ, cosh(δ) and sinh(δ) would cancel in the components
+    // along the smaller eigenvalue. Instead each exponential multiplies its own spectral projector
+    // (δ I ± B) / 2δ, which also keeps e^μ from overflowing against cosh(δ)
+    let (p, m) = ((mu + delta).exp(), (mu - delta).exp());
+    let P = (B + delta * DMat2::IDENTITY) * (0.5 / delta);
+    let M = (delta * DMat2::IDENTITY - B) * (0.5 / delta);
+    return p * P + m * M;
+  }
+
+  let (c, s) = if delta_sq > 0.0 {
+    (mu.exp() * delta.cosh(), mu.exp() * delta.sinh() / delta)
+  } else if delta_sq < 0.0 {
+    (mu.exp() * delta.cos(), mu.exp() * delta.sin() / delta)
+  } else {
+    (mu.exp(), mu.exp())
+  };
+
+  c * DMat2::IDENTITY + s * B
+}
+
+/// Computes the exponential of a dense 3x3 matrix
+// Skew-symmetric matrices [ω]× are infinitesimal rotations, and exponentiate to the rotation about ω
+// by the angle |ω| as given by the Rodrigues formula
+pub(crate) fn exp3(A: DMat3) -> DMat3 {
+  if A + A.transpose() == DMat3::ZERO {
+    let omega = DVec3::new(A.y_axis.z, A.z_axis.x, A.x_axis.y);
+    let theta = omega.length();
+    if theta == 0.0 {
+      return DMat3::IDENTITY;
+    }
+    return DMat3::from_axis_angle(omega / theta, theta);
+  }
+
+  exp_pade(A)
+}
+
+/// Computes the exponential of a dense 4x4 matrix
+pub(crate) fn exp4(A: DMat4) -> DMat4 {
+  exp_pade(A)
+}
+
+// The bounds on ‖A‖₁ for which the Padé approximant of each degree is accurate to double precision
+const THETA: [(usize, f64); 4] = [
+  (3, 1.495585217958292e-2),
+  (5, 2.53939833006323e-1),
+  (7, 9.504178996162932e-1),
+  (9, 2.097847961257068),
+];
+const THETA13: f64 = 5.371920351148152;
+
+// The coefficients of the numerator of the Padé approximants, with the denominator q(A) = p(-A)
+const PADE3: [f64; 4] = [120.0, 60.0, 12.0, 1.0];
+const PADE5: [f64; 6] = [30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+const PADE7: [f64; 8] = [
+  17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+];
+const PADE9: [f64; 10] = [
+  17643225600.0,
+  8821612800.0,
+  2075673600.0,
+  302702400.0,
+  30270240.0,
+  2162160.0,
+  110880.0,
+  3960.0,
+  90.0,
+  1.0,
+];
+const PADE13: [f64; 14] = [
+  64764752532480000.0,
+  32382376266240000.0,
+  7771770303897600.0,
+  1187353796428800.0,
+  129060195264000.0,
+  10559470521600.0,
+  670442572800.0,
+  33522128640.0,
+  1323241920.0,
+  40840800.0,
+  960960.0,
+  16380.0,
+  182.0,
+  1.0,
+];
+
+/// Computes the matrix exponential by scaling and squaring with a Padé approximant
+// This is the algorithm of Higham, "The scaling and squaring method for the matrix exponential
+// revisited" (2005). The lowest degree accurate for ‖A‖₁ is used, and larger matrices are scaled by 2^-s
+// to fit the degree 13 approximant, whose result is squared s times
+// Non-finite input gives a NaN matrix, as no finite number of squarings would suffice
+pub(crate) fn exp_pade<M: SquareMatrix>(A: M) -> M {
+  let norm = A.norm1();
+  let I = M::IDENTITY;
+  if !norm.is_finite() {
+    return I * f64::NAN;
+  }
+
+  for (m, theta) in THETA {
+    if norm <= theta {
+      let b = match m {
+        3 => &PADE3[..],
+        5 => &PADE5[..],
+        7 => &PADE7[..],
+        _ => &PADE9[..],
+      };
+
+      let A2 = A * A;
+      let mut power = I;
+      let (mut U, mut V) = (I * b[1], I * b[0]);
+      for k in 1..=m / 2 {
+        power = power * A2;
+        U = U + power * b[2 * k + 1];
+        V = V + power * b[2 * k];
+      }
+      let U = A * U;
+
+      return (V - U).solve(V + U);
+    }
+  }
+
+  let s = (norm / THETA13).log2().ceil().max(0.0) as i32;
+  let A = A * 2f64.powi(-s);
+  let b = PADE13;
+
+  let A2 = A * A;
+  let A4 = A2 * A2;
+  let A6 = A4 * A2;
+  let U = A6 * (A6 * b[13] + A4 * b[11] + A2 * b[9]) + A6 * b[7] + A4 * b[5] + A2 * b[3] + I * b[1];
+  let U = A * U;
+  let V = A6 * (A6 * b[12] + A4 * b[10] + A2 * b[8]) + A6 * b[6] + A4 * b[4] + A2 * b[2] + I * b[0];
+
+  let mut X = (V - U).solve(V + U);
+  for _ in 0..s {
+    X = X * X;
+  }
+  X
+}
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Computes the principal square root with the scaled Denman-Beavers iteration, assuming it exists
+// The coupled iteration Y <- (Y + Z⁻¹) / 2, Z <- (Z + Y⁻¹) / 2 starting from Y = A, Z = I converges
+// quadratically to Y = A^(1/2) and Z = A^(-1/2). While far from convergence, the iterates are scaled by
+// |det(Y) det(Z)|^(-1/2n), which greatly reduces the iteration count for badly scaled matrices
+pub(crate) fn sqrt_denman_beavers<M: SquareMatrix>(A: M, n: usize) -> Result<M, LinalgError> {
+  let I = M::IDENTITY;
+  let (mut Y, mut Z) = (A, I);
+  let mut converging = false;
+
+  for i in 0..MAX_ITERATIONS {
+    let (Y_inv, Z_inv) = (Y.solve(I), Z.solve(I));
+    // The determinants may overflow or underflow for badly scaled matrices, and the step is then unscaled
+    let mu = (Y.det() * Z.det()).abs().powf(-0.5 / n as f64);
+    let mu = if converging || !(mu.is_finite() && mu > 0.0) {
+      1.0
+    } else {
+      mu
+    };
+
+    let Y_next = (Y * mu + Z_inv * mu.recip()) * 0.5;
+    Z = (Z * mu + Y_inv * mu.recip()) * 0.5;
+
+    let delta = (Y_next - Y).norm1();
+    Y = Y_next;
+
+    // Convergence is quadratic, so a single step past this point reaches full accuracy
+    if converging {
+      return Ok(Y);
+    }
+    // The norm skips NaN entries, so the iterate itself is checked as well
+    if !delta.is_finite() || !Y.is_finite() {
+      return Err(LinalgError::NoConvergence { iterations: i + 1 });
+    }
+    converging = delta <= 1e-8 * Y.norm1();
+  }
+
+  Err(LinalgError::NoConvergence {
+    iterations: MAX_ITERATIONS,
+  })
+}
+
+// The bound on ‖X‖₁ for which the degree 7 Padé approximant of log(I + X) is accurate to double precision
+const LOG_THETA7: f64 = 0.264;
+
+// The Gauss-Legendre nodes and weights on [0, 1] of the degree 7 rule
+const GAUSS_LEGENDRE7: [(f64, f64); 7] = [
+  (0.025446043828620757, 0.06474248308443484),
+  (0.12923440720030277, 0.13985269574463835),
+  (0.2970774243113014, 0.19091502525255952),
+  (0.5, 0.2089795918367347),
+  (0.7029225756886985, 0.19091502525255952),
+  (0.8707655927996972, 0.13985269574463835),
+  (0.9745539561713792, 0.06474248308443484),
+];
+
+/// Computes the principal logarithm with inverse scaling and squaring
+// Square roots are taken k times until A^(1/2^k) is close to I, then log(A) = 2^k log(I + X) with
+// X = A^(1/2^k) - I. The logarithm of I + X is the [7/7] Padé approximant, evaluated as the
+// Gauss-Legendre quadrature of log(I + X) = ∫₀¹ X (I + t X)⁻¹ dt, see Higham, "Functions of Matrices"
+pub(crate) fn log_inverse_scaling<M: SquareMatrix>(A: M, n: usize) -> Result<M, LinalgError> {
+  A.check_principal()?;
+
+  let I = M::IDENTITY;
+  let mut X = A;
+  let mut k = 0;
+
+  // A failing square root reports its own Denman-Beavers iterations, while running out of square roots
+  // reports the number taken
+  while (X - I).norm1() > LOG_THETA7 {
+    if k == MAX_ITERATIONS {
+      return Err(LinalgError::NoConvergence { iterations: k });
+    }
+    X = sqrt_denman_beavers(X, n)?;
+    k += 1;
+  }
+
+  let X = X - I;
+  let L = GAUSS_LEGENDRE7
+    .map(|(t, w)| (I + X * t).solve(X) * w)
+    .into_iter()
+    .reduce(|a, b| a + b)
+    .unwrap();
+
+  Ok(L * 2f64.powi(k as i32))
+}
+
+/// Computes the principal square root of a dense 2x2 matrix
+pub(crate) fn sqrt2(A: DMat2) -> Result<DMat2, LinalgError> {
+  A.check_principal()?;
+  sqrt_denman_beavers(A, 2)
+}
+
+/// Computes the principal square root of a dense 3x3 matrix
+pub(crate) fn sqrt3(A: DMat3) -> Result<DMat3, LinalgError> {
+  A.check_principal()?;
+  sqrt_denman_beavers(A, 3)
+}
+
+/// Computes the principal square root of a dense 4x4 matrix
+pub(crate) fn sqrt4(A: DMat4) -> Result<DMat4, LinalgError> {
+  A.check_principal()?;
+  sqrt_denman_beavers(A, 4)
+}
+
+/// Computes the principal logarithm of a dense 2x2 matrix
+pub(crate) fn log2(A: DMat2) -> Result<DMat2, LinalgError> {
+  log_inverse_scaling(A, 2)
+}
+
+/// Computes the principal logarithm of a dense 3x3 matrix
+pub(crate) fn log3(A: DMat3) -> Result<DMat3, LinalgError> {
+  log_inverse_scaling(A, 3)
+}
+
+/// Computes the principal logarithm of a dense 4x4 matrix
+pub(crate) fn log4(A: DMat4) -> Result<DMat4, LinalgError> {
+  log_inverse_scaling(A, 4)
+}
@@ -0,0 +1,144 @@
+use crate::qr::householder2;
+use crate::svd::singular_values_jacobi;
+
+use glam::{dvec2, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+
+/// A streaming least-squares fit minimizing ‖A x - y‖ over rows of A and targets y added one at a time
+// The rows are folded into the triangular factor R of A = Q R, along with z = Qᵀ y. Only O(N²) state
+// is kept, and the normal equations AᵀA x = Aᵀy are never formed, which would square the condition number
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LeastSquares<const N: usize> {
+  R: [[f64; N]; N],
+  z: [f64; N],
+  residual_sq: f64,
+  rows: usize,
+}
+
+impl<const N: usize> Default for LeastSquares<N> {
+  fn default() -> Self {
+    Self {
+      R: [[0.0; N]; N],
+      z: [0.0; N],
+      residual_sq: 0.0,
+      rows: 0,
+    }
+  }
+}
+
+impl<const N: usize> LeastSquares<N> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the number of rows added so far
+  pub fn rows(&self) -> usize {
+    self.rows
+  }
+
+  /// Returns the norm of the residual A x - y at the least-squares solution
+  pub fn residual_norm(&self) -> f64 {
+    self.residual_sq.sqrt()
+  }
+
+  /// Returns the numerical rank of A, counting the singular values above max(m, N) ε σ_max for m rows
+  pub fn rank(&self) -> usize {
+    let sigma = singular_values_jacobi(self.R);
+    let tol = self.rows.max(N) as f64 * f64::EPSILON * sigma[0];
+    sigma.iter().filter(|&&s| s > tol).count()
+  }
+
+  /// Returns true if A does not have full column rank, in which case the solution is not unique
+  pub fn is_rank_deficient(&self) -> bool {
+    self.rank() < N
+  }
+
+  // Annihilates a new row against R with a householder reflection per column
+  fn add_row_array(&mut self, mut a: [f64; N], mut y: f64) {
+    for k in 0..N {
+      let H = householder2(dvec2(self.R[k][k], a[k]));
+      for (col, a) in self.R.iter_mut().zip(a.iter_mut()).skip(k) {
+        let v = H * dvec2(col[k], *a);
+        (col[k], *a) = (v.x, v.y);
+      }
+      let v = H * dvec2(self.z[k], y);
+      (self.z[k], y) = (v.x, v.y);
+    }
+
+    self.residual_sq += y * y;
+    self.rows += 1;
+  }
+
+  // Solves R x = z by back substitution
+  fn solve_array(&self) -> Option<[f64; N]> {
+    if self.is_rank_deficient() {
+      return None;
+    }
+
+    let mut x = self.z;
+    for j in (0..N).rev() {
+      x[j] /= self.R[j][j];
+      for i in 0..j {
+        x[i] -= self.R[j][i] * x[j];
+      }
+    }
+    Some(x)
+  }
+
+  // Computes σ² (AᵀA)⁻¹ = σ² R⁻¹ R⁻ᵀ, with the residual variance σ² = ‖A x - y‖² / (m - N)
+  fn covariance_array(&self) -> Option<[[f64; N]; N]> {
+    if self.rows <= N || self.is_rank_deficient() {
+      return None;
+    }
+
+    // The columns of R⁻¹, by back substitution against the standard basis
+    let mut R_inv = [[0.0; N]; N];
+    for (k, x) in R_inv.iter_mut().enumerate() {
+      x[k] = 1.0;
+      for j in (0..=k).rev() {
+        x[j] /= self.R[j][j];
+        for i in 0..j {
+          x[i] -= self.R[j][i] * x[j];
+        }
+      }
+    }
+
+    let variance = self.residual_sq / (self.rows - N) as f64;
+    Some(std::array::from_fn(|j| {
+      std::array::from_fn(|i| variance * (0..N).map(|k| R_inv[k][i] * R_inv[k][j]).sum::<f64>())
+    }))
+  }
+}
+
+macro_rules! impl_least_squares {
+  ($num:expr, $Mat:ty, $Vec:ty) => {
+    impl LeastSquares<$num> {
+      /// Adds the equation a · x = y
+      pub fn add_row(&mut self, a: $Vec, y: f64) {
+        self.add_row_array(a.into(), y);
+      }
+
+      /// Adds the equation a · x = y with weight w, which scales its squared residual
+      pub fn add_weighted_row(&mut self, a: $Vec, y: f64, w: f64) {
+        let w = w.sqrt();
+        self.add_row_array((w * a).into(), w * y);
+      }
+
+      /// Returns the parameters x minimizing ‖A x - y‖, or None if A is rank deficient
+      pub fn solve(&self) -> Option<$Vec> {
+        self.solve_array().map(<$Vec>::from)
+      }
+
+      /// Returns the covariance of the parameters, estimating the noise variance from the residual.
+      /// Returns None if A is rank deficient or there are no more rows than parameters
+      pub fn covariance(&self) -> Option<$Mat> {
+        self
+          .covariance_array()
+          .map(|C| <$Mat>::from_cols_array_2d(&C))
+      }
+    }
+  };
+}
+
+impl_least_squares!(2, DMat2, DVec2);
+impl_least_squares!(3, DMat3, DVec3);
+impl_least_squares!(4, DMat4, DVec4);
@@ -1,79 +1,1057 @@
 #![allow(non_snake_case)]
 
+mod cholesky;
+mod complex;
 mod eigen;
 mod eigen_symmetric;
+mod error;
+mod functions;
+mod least_squares;
+mod lu;
+mod norms;
+mod options;
+mod order;
+mod polar;
+mod predicates;
+mod qr;
+mod single;
+mod spectrum;
+mod svd;
 #[cfg(test)]
 mod tests;
 mod utils;
 
 use eigen::*;
 use eigen_symmetric::*;
+use functions::*;
+use norms::*;
+use polar::*;
+use predicates::*;
+use qr::*;
+use svd::*;
+use utils::Real;
 
-use glam::{DMat2, DMat3, DMat4, DVec2};
+pub use cholesky::{Cholesky, Ldlt};
+pub use complex::{Complex, Complex32, Complex64};
+pub use error::LinalgError;
+pub use least_squares::LeastSquares;
+pub use lu::Lu;
+pub use options::{EigenDiagnostics, EigenOptions};
+pub use order::EigenOrder;
+pub use spectrum::{Eigenvalue, Spectrum};
+
+use glam::{DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, Mat2, Mat3, Mat3A, Mat4};
 
 pub trait DMatExt {
   fn trace(&self) -> f64;
+
+  // The operator norm induced by the vector 1-norm, the maximum absolute column sum
+  fn norm1(&self) -> f64;
+
+  // The operator norm induced by the vector ∞-norm, the maximum absolute row sum
+  fn norm_inf(&self) -> f64;
+
+  fn norm_frobenius(&self) -> f64;
+
+  // The largest absolute entry, which is not an operator norm
+  fn norm_max(&self) -> f64;
+
+  // The operator norm induced by the Euclidean norm, the largest singular value
+  fn norm2(&self) -> f64;
+
+  // The condition numbers ‖A‖ ‖A⁻¹‖ in the 1- and 2-norm, which are infinite for singular matrices
+  fn cond1(&self) -> f64;
+
+  fn cond2(&self) -> f64;
+
+  // The largest magnitude of the eigenvalues. It is NaN for non-finite input, and also if the QR algorithm
+  // fails to converge, which try_eigvals reports as LinalgError::NoConvergence
+  fn spectral_radius(&self) -> f64;
+
+  // The structural predicates compare entries with the absolute tolerance tol
+  fn is_symmetric(&self, tol: f64) -> bool;
+
+  fn is_skew_symmetric(&self, tol: f64) -> bool;
+
+  // Whether AᵀA = I
+  fn is_orthogonal(&self, tol: f64) -> bool;
+
+  // Whether A is orthogonal with det(A) = +1
+  fn is_rotation(&self, tol: f64) -> bool;
+
+  // Whether A is symmetric within tol, and the eigenvalues of its symmetric part (A + Aᵀ) / 2 all exceed
+  // tol. The one tolerance serves both as the absolute tolerance on the entries and as the positivity bound
+  fn is_positive_definite(&self, tol: f64) -> bool;
+
+  fn is_upper_triangular(&self, tol: f64) -> bool;
+
+  // Whether the entries below the first subdiagonal vanish
+  fn is_hessenberg(&self, tol: f64) -> bool;
+
+  fn is_diagonal(&self, tol: f64) -> bool;
+}
+
+// Implements the methods of DMatExt and MatExt shared by all sizes, given the singular values in descending order
+macro_rules! impl_mat_ext {
+  // The f32 singular values are found with the Jacobi method for every size
+  ($T:ident, $Mat:ty) => {
+    impl_mat_ext!($T, $Mat, |A: &$Mat| singular_values_jacobi(
+      A.to_cols_array_2d()
+    ));
+  };
+  ($T:ident, $Mat:ty, $singular_values:expr) => {
+    fn norm1(&self) -> $T {
+      norm1(&self.to_cols_array_2d())
+    }
+
+    fn norm_inf(&self) -> $T {
+      norm_inf(&self.to_cols_array_2d())
+    }
+
+    fn norm_frobenius(&self) -> $T {
+      norm_frobenius(&self.to_cols_array_2d())
+    }
+
+    fn norm_max(&self) -> $T {
+      norm_max(&self.to_cols_array_2d())
+    }
+
+    fn norm2(&self) -> $T {
+      ($singular_values)(self)[0]
+    }
+
+    fn cond1(&self) -> $T {
+      cond1(&self.to_cols_array_2d())
+    }
+
+    fn cond2(&self) -> $T {
+      cond2(&($singular_values)(self))
+    }
+
+    fn spectral_radius(&self) -> $T {
+      match self.try_eigvals() {
+        Ok(eigvals) => eigvals
+          .iter()
+          .fold(0.0, |max, lambda| max.max(lambda.abs())),
+        // Both non-finite input and non-convergence give NaN
+        Err(_) => $T::NAN,
+      }
+    }
+
+    fn is_symmetric(&self, tol: $T) -> bool {
+      is_symmetric(&self.to_cols_array_2d(), tol)
+    }
+
+    fn is_skew_symmetric(&self, tol: $T) -> bool {
+      is_skew_symmetric(&self.to_cols_array_2d(), tol)
+    }
+
+    fn is_orthogonal(&self, tol: $T) -> bool {
+      (self.transpose() * *self).abs_diff_eq(<$Mat>::IDENTITY, tol)
+    }
+
+    fn is_rotation(&self, tol: $T) -> bool {
+      self.is_orthogonal(tol) && self.determinant() > 0.0
+    }
+
+    fn is_positive_definite(&self, tol: $T) -> bool {
+      // The symmetric part is exactly symmetric, so the symmetry assertion cannot panic within tol
+      let S = (*self + self.transpose()) * 0.5;
+      self.is_symmetric(tol)
+        && S
+          .try_eigvals_symmetric()
+          .is_ok_and(|eigvals| eigvals.iter().all(|&lambda| lambda > tol))
+    }
+
+    fn is_upper_triangular(&self, tol: $T) -> bool {
+      is_lower_bandwidth(&self.to_cols_array_2d(), 0, tol)
+    }
+
+    fn is_hessenberg(&self, tol: $T) -> bool {
+      is_lower_bandwidth(&self.to_cols_array_2d(), 1, tol)
+    }
+
+    fn is_diagonal(&self, tol: $T) -> bool {
+      is_diagonal(&self.to_cols_array_2d(), tol)
+    }
+  };
 }
 
 impl DMatExt for DMat2 {
   fn trace(&self) -> f64 {
     self.x_axis.x + self.y_axis.y
   }
+
+  impl_mat_ext!(f64, DMat2, DMat2::singular_values);
 }
 
 impl DMatExt for DMat3 {
   fn trace(&self) -> f64 {
     self.x_axis.x + self.y_axis.y + self.z_axis.z
   }
+
+  impl_mat_ext!(f64, DMat3, DMat3::singular_values);
 }
 
 impl DMatExt for DMat4 {
   fn trace(&self) -> f64 {
     self.x_axis.x + self.y_axis.y + self.z_axis.z + self.w_axis.w
   }
+
+  impl_mat_ext!(f64, DMat4, DMat4::singular_values);
+}
+
+// The counterpart of DMatExt for the f32 matrices, computed in single precision
+pub trait MatExt {
+  fn trace(&self) -> f32;
+
+  fn norm1(&self) -> f32;
+
+  fn norm_inf(&self) -> f32;
+
+  fn norm_frobenius(&self) -> f32;
+
+  fn norm_max(&self) -> f32;
+
+  fn norm2(&self) -> f32;
+
+  fn cond1(&self) -> f32;
+
+  fn cond2(&self) -> f32;
+
+  fn spectral_radius(&self) -> f32;
+
+  fn is_symmetric(&self, tol: f32) -> bool;
+
+  fn is_skew_symmetric(&self, tol: f32) -> bool;
+
+  fn is_orthogonal(&self, tol: f32) -> bool;
+
+  fn is_rotation(&self, tol: f32) -> bool;
+
+  fn is_positive_definite(&self, tol: f32) -> bool;
+
+  fn is_upper_triangular(&self, tol: f32) -> bool;
+
+  fn is_hessenberg(&self, tol: f32) -> bool;
+
+  fn is_diagonal(&self, tol: f32) -> bool;
+}
+
+impl MatExt for Mat2 {
+  fn trace(&self) -> f32 {
+    self.x_axis.x + self.y_axis.y
+  }
+
+  impl_mat_ext!(f32, Mat2);
 }
 
+impl MatExt for Mat3 {
+  fn trace(&self) -> f32 {
+    self.x_axis.x + self.y_axis.y + self.z_axis.z
+  }
+
+  impl_mat_ext!(f32, Mat3);
+}
+
+impl MatExt for Mat3A {
+  fn trace(&self) -> f32 {
+    self.x_axis.x + self.y_axis.y + self.z_axis.z
+  }
+
+  impl_mat_ext!(f32, Mat3A);
+}
+
+impl MatExt for Mat4 {
+  fn trace(&self) -> f32 {
+    self.x_axis.x + self.y_axis.y + self.z_axis.z + self.w_axis.w
+  }
+
+  impl_mat_ext!(f32, Mat4);
+}
+
+// Mirrors glam's assertions, which are enabled by the glam-assert feature, or by the debug-glam-assert
+// feature in debug builds
+macro_rules! glam_assert {
+  ($($arg:tt)*) => {
+    #[cfg(any(feature = "glam-assert", all(debug_assertions, feature = "debug-glam-assert")))]
+    assert!($($arg)*);
+  };
+}
+
+// The relative tolerance allows for rounding in f32 input. Non-finite input is not checked, as it gives
+// NaN results or NonFiniteInput instead
+macro_rules! glam_assert_symmetric {
+  ($A:expr) => {
+    glam_assert!(
+      !$A.is_finite() || $A.is_symmetric(1e-6 * $A.norm_max()),
+      "the matrix is not symmetric"
+    );
+  };
+}
+
+pub(crate) use {glam_assert, glam_assert_symmetric};
+
+// The methods without a try_ prefix give NaN eigenvalues and eigenvectors for non-finite input, for every
+// size, and panic if the QR algorithm fails to converge
 pub trait EigenDecomposition<const N: usize> {
-  fn eigvals(&self) -> [DVec2; N];
+  // The scalar types of the results, with complex eigenvalues represented by Complex
+  type Real;
+  type Complex;
+
+  fn eigvals(&self) -> [Self::Complex; N];
+
+  // The eigenvalues with real roots distinguished from conjugate pairs, as determined by the QR algorithm
+  fn spectrum(&self) -> Spectrum<Self::Real, N>;
+
+  // The eigenvectors are returned as the real and imaginary parts of the columns of two matrices,
+  // so that the k-th column of each corresponds to the k-th eigenvalue
+  fn eig(&self) -> ([Self::Complex; N], Self, Self)
+  where
+    Self: Sized;
+
+  // A fallible variant of eigvals, returning an error for non-finite input or if the iteration fails to converge
+  fn try_eigvals(&self) -> Result<[Self::Complex; N], LinalgError>;
+
+  fn try_spectrum(&self) -> Result<Spectrum<Self::Real, N>, LinalgError>;
+
+  // Runs the QR algorithm with the given deflation tolerance, iteration limit and shift strategy, returning
+  // the eigenvalues in ascending order along with diagnostics of the run. The 2x2 eigenvalues are found in
+  // closed form instead, so the options have no effect there, and the diagnostics report no iterations
+  // and the subdiagonal entry of the matrix itself
+  fn eigvals_with(
+    &self,
+    options: &EigenOptions,
+  ) -> Result<([Self::Complex; N], EigenDiagnostics<N>), LinalgError>;
 
   // The symmetric cases exhibit nice properties, allowing for specialized algorithms
   // Additionally, symmetric matrices always have real eigenvalues, allowing for a simpler return type
-  // TODO: Implement glam_assert to assure symmetry
-  fn eigvals_symmetric(&self) -> [f64; N];
+  // Non-symmetric input is caught by the glam-assert and debug-glam-assert features
+  fn eigvals_symmetric(&self) -> [Self::Real; N];
+
+  fn try_eigvals_symmetric(&self) -> Result<[Self::Real; N], LinalgError>;
+
+  // The eigenvectors are returned as the columns of an orthonormal matrix V, so that A = V diag(λ) Vᵀ
+  fn eig_symmetric(&self) -> ([Self::Real; N], Self)
+  where
+    Self: Sized;
+
+  // The methods above return the eigenvalues in ascending order, as in EigenOrder::Ascending
+  // The ordered variants take an explicit order, and permute the eigenvectors to match
+  fn eigvals_ordered(&self, order: EigenOrder) -> [Self::Complex; N];
+
+  fn eig_ordered(&self, order: EigenOrder) -> ([Self::Complex; N], Self, Self)
+  where
+    Self: Sized;
+
+  fn eigvals_symmetric_ordered(&self, order: EigenOrder) -> [Self::Real; N];
+
+  fn eig_symmetric_ordered(&self, order: EigenOrder) -> ([Self::Real; N], Self)
+  where
+    Self: Sized;
+}
+
+// Implements the ordered eigenvalue methods by permuting the results of the ascending ones
+macro_rules! impl_eigen_ordered {
+  ($n:literal, $T:ident, $Complex:ident, $Mat:ty) => {
+    /// Computes the eigenvalues of a dense matrix in the given order
+    fn eigvals_ordered(&self, order: EigenOrder) -> [$Complex; $n] {
+      let eigvals = self.eigvals();
+      order.argsort_complex(&eigvals).map(|i| eigvals[i])
+    }
+
+    /// Computes the eigenvalues and unit eigenvectors of a dense matrix in the given order
+    fn eig_ordered(&self, order: EigenOrder) -> ([$Complex; $n], $Mat, $Mat) {
+      let (eigvals, re, im) = self.eig();
+      let perm = order.argsort_complex(&eigvals);
+      (
+        perm.map(|i| eigvals[i]),
+        <$Mat>::from_cols_array_2d(&perm.map(|i| re.col(i).to_array())),
+        <$Mat>::from_cols_array_2d(&perm.map(|i| im.col(i).to_array())),
+      )
+    }
+
+    /// Computes the eigenvalues of a dense, symmetric matrix in the given order
+    fn eigvals_symmetric_ordered(&self, order: EigenOrder) -> [$T; $n] {
+      let eigvals = self.eigvals_symmetric();
+      order.argsort(&eigvals).map(|i| eigvals[i])
+    }
+
+    /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric matrix in the given order
+    fn eig_symmetric_ordered(&self, order: EigenOrder) -> ([$T; $n], $Mat) {
+      let (eigvals, V) = self.eig_symmetric();
+      let perm = order.argsort(&eigvals);
+      (
+        perm.map(|i| eigvals[i]),
+        <$Mat>::from_cols_array_2d(&perm.map(|i| V.col(i).to_array())),
+      )
+    }
+  };
+}
+
+pub(crate) use impl_eigen_ordered;
+
+// The implementations for each size, generated for the f64 matrices here and for the f32 matrices in the
+// single module, where the algorithms of the same names are the f32 ones
+macro_rules! impl_eigen_decomposition2 {
+  ($T:ident, $Complex:ident, $Mat:ty) => {
+    impl EigenDecomposition<2> for $Mat {
+      type Real = $T;
+      type Complex = $Complex;
+
+      /// Computes the eigenvalues of a dense 2x2 matrix
+      fn eigvals(&self) -> [$Complex; 2] {
+        self.spectrum().to_complex()
+      }
+
+      /// Computes the eigenvalues of a dense 2x2 matrix, classified into real eigenvalues and conjugate pairs
+      fn spectrum(&self) -> Spectrum<$T, 2> {
+        if !self.is_finite() {
+          return Spectrum::from_real([$T::NAN; 2]);
+        }
+        eigvals2(*self)
+      }
+
+      /// Computes the eigenvalues and unit eigenvectors of a dense 2x2 matrix
+      fn eig(&self) -> ([$Complex; 2], $Mat, $Mat) {
+        eig2(*self)
+      }
+
+      /// Computes the eigenvalues of a dense 2x2 matrix, failing on non-finite input
+      fn try_eigvals(&self) -> Result<[$Complex; 2], LinalgError> {
+        Ok(self.try_spectrum()?.to_complex())
+      }
+
+      /// Computes the spectrum of a dense 2x2 matrix, failing on non-finite input
+      fn try_spectrum(&self) -> Result<Spectrum<$T, 2>, LinalgError> {
+        if !self.is_finite() {
+          return Err(LinalgError::NonFiniteInput);
+        }
+        Ok(eigvals2(*self))
+      }
+
+      /// Computes the eigenvalues of a dense 2x2 matrix in closed form, ignoring the options
+      fn eigvals_with(
+        &self,
+        _options: &EigenOptions,
+      ) -> Result<([$Complex; 2], EigenDiagnostics<2>), LinalgError> {
+        // No QR iterations run, and the matrix is its own final iterate
+        let eigvals = self.try_spectrum()?.to_complex();
+        Ok((
+          eigvals,
+          EigenDiagnostics::new(0, &[self.x_axis.y.abs().to_f64()]),
+        ))
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 2x2 matrix
+      fn eigvals_symmetric(&self) -> [$T; 2] {
+        if !self.is_finite() {
+          return [$T::NAN; 2];
+        }
+        glam_assert_symmetric!(self);
+        eigvals2_symmetric(*self)
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 2x2 matrix, failing on non-finite input
+      fn try_eigvals_symmetric(&self) -> Result<[$T; 2], LinalgError> {
+        if !self.is_finite() {
+          return Err(LinalgError::NonFiniteInput);
+        }
+        glam_assert_symmetric!(self);
+        Ok(eigvals2_symmetric(*self))
+      }
+
+      /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 2x2 matrix
+      fn eig_symmetric(&self) -> ([$T; 2], $Mat) {
+        glam_assert_symmetric!(self);
+        eig2_symmetric(*self)
+      }
+
+      impl_eigen_ordered!(2, $T, $Complex, $Mat);
+    }
+  };
+}
+
+pub(crate) use impl_eigen_decomposition2;
+
+macro_rules! impl_eigen_decomposition3 {
+  ($T:ident, $Complex:ident, $Mat:ty) => {
+    impl EigenDecomposition<3> for $Mat {
+      type Real = $T;
+      type Complex = $Complex;
+
+      /// Computes the eigenvalues of a dense 3x3 matrix
+      fn eigvals(&self) -> [$Complex; 3] {
+        self.spectrum().to_complex()
+      }
+
+      /// Computes the eigenvalues of a dense 3x3 matrix, classified into real eigenvalues and conjugate pairs
+      fn spectrum(&self) -> Spectrum<$T, 3> {
+        if !self.is_finite() {
+          return Spectrum::from_real([$T::NAN; 3]);
+        }
+        self.try_spectrum().unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues and unit eigenvectors of a dense 3x3 matrix
+      fn eig(&self) -> ([$Complex; 3], $Mat, $Mat) {
+        eig3(*self).unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues of a dense 3x3 matrix, failing on non-finite input or non-convergence
+      fn try_eigvals(&self) -> Result<[$Complex; 3], LinalgError> {
+        Ok(self.try_spectrum()?.to_complex())
+      }
+
+      /// Computes the spectrum of a dense 3x3 matrix, failing on non-finite input or non-convergence
+      fn try_spectrum(&self) -> Result<Spectrum<$T, 3>, LinalgError> {
+        let (spectrum, _) = eigvals3(*self, &EigenOptions::default())?;
+        Ok(spectrum)
+      }
+
+      /// Computes the eigenvalues of a dense 3x3 matrix with the given options, along with diagnostics
+      fn eigvals_with(
+        &self,
+        options: &EigenOptions,
+      ) -> Result<([$Complex; 3], EigenDiagnostics<3>), LinalgError> {
+        let (spectrum, diagnostics) = eigvals3(*self, options)?;
+        Ok((spectrum.to_complex(), diagnostics))
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 3x3 matrix
+      fn eigvals_symmetric(&self) -> [$T; 3] {
+        if !self.is_finite() {
+          return [$T::NAN; 3];
+        }
+        self
+          .try_eigvals_symmetric()
+          .unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 3x3 matrix, failing on non-finite input or non-convergence
+      fn try_eigvals_symmetric(&self) -> Result<[$T; 3], LinalgError> {
+        if !self.is_finite() {
+          return Err(LinalgError::NonFiniteInput);
+        }
+        glam_assert_symmetric!(self);
+        eigvals3_symmetric(*self)
+      }
+
+      /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 3x3 matrix
+      fn eig_symmetric(&self) -> ([$T; 3], $Mat) {
+        glam_assert_symmetric!(self);
+        eig3_symmetric(*self).unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      impl_eigen_ordered!(3, $T, $Complex, $Mat);
+    }
+  };
+}
+
+pub(crate) use impl_eigen_decomposition3;
+
+macro_rules! impl_eigen_decomposition4 {
+  ($T:ident, $Complex:ident, $Mat:ty) => {
+    impl EigenDecomposition<4> for $Mat {
+      type Real = $T;
+      type Complex = $Complex;
+
+      /// Computes the eigenvalues of a dense 4x4 matrix
+      fn eigvals(&self) -> [$Complex; 4] {
+        self.spectrum().to_complex()
+      }
+
+      /// Computes the eigenvalues of a dense 4x4 matrix, classified into real eigenvalues and conjugate pairs
+      fn spectrum(&self) -> Spectrum<$T, 4> {
+        if !self.is_finite() {
+          return Spectrum::from_real([$T::NAN; 4]);
+        }
+        self.try_spectrum().unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues and unit eigenvectors of a dense 4x4 matrix
+      fn eig(&self) -> ([$Complex; 4], $Mat, $Mat) {
+        eig4(*self).unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues of a dense 4x4 matrix, failing on non-finite input or non-convergence
+      fn try_eigvals(&self) -> Result<[$Complex; 4], LinalgError> {
+        Ok(self.try_spectrum()?.to_complex())
+      }
+
+      /// Computes the spectrum of a dense 4x4 matrix, failing on non-finite input or non-convergence
+      fn try_spectrum(&self) -> Result<Spectrum<$T, 4>, LinalgError> {
+        let (spectrum, _) = eigvals4(*self, &EigenOptions::default())?;
+        Ok(spectrum)
+      }
+
+      /// Computes the eigenvalues of a dense 4x4 matrix with the given options, along with diagnostics
+      fn eigvals_with(
+        &self,
+        options: &EigenOptions,
+      ) -> Result<([$Complex; 4], EigenDiagnostics<4>), LinalgError> {
+        let (spectrum, diagnostics) = eigvals4(*self, options)?;
+        Ok((spectrum.to_complex(), diagnostics))
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 4x4 matrix
+      fn eigvals_symmetric(&self) -> [$T; 4] {
+        if !self.is_finite() {
+          return [$T::NAN; 4];
+        }
+        self
+          .try_eigvals_symmetric()
+          .unwrap_or_else(|e| panic!("{e}"))
+      }
+
+      /// Computes the eigenvalues of a dense, symmetric 4x4 matrix, failing on non-finite input or non-convergence
+      fn try_eigvals_symmetric(&self) -> Result<[$T; 4], LinalgError> {
+        if !self.is_finite() {
+          return Err(LinalgError::NonFiniteInput);
+        }
+        glam_assert_symmetric!(self);
+        eigvals4_symmetric(*self)
+      }
+
+      /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 4x4 matrix
+      fn eig_symmetric(&self) -> ([$T; 4], $Mat) {
+        glam_assert_symmetric!(self);
+        eig4_symmetric(*self)
+      }
+
+      impl_eigen_ordered!(4, $T, $Complex, $Mat);
+    }
+  };
+}
+
+pub(crate) use impl_eigen_decomposition4;
+
+impl_eigen_decomposition2!(f64, Complex64, DMat2);
+impl_eigen_decomposition3!(f64, Complex64, DMat3);
+impl_eigen_decomposition4!(f64, Complex64, DMat4);
+
+pub trait QrDecomposition {
+  // Returns (Q, R) with A = Q R, where Q is orthogonal and R is upper triangular
+  // Zero and linearly dependent columns are allowed, and give vanishing diagonal entries of R
+  fn qr(&self) -> (Self, Self)
+  where
+    Self: Sized;
+}
+
+impl QrDecomposition for DMat2 {
+  /// Computes the QR decomposition of a dense 2x2 matrix
+  fn qr(&self) -> (DMat2, DMat2) {
+    qr2(*self)
+  }
+}
+
+impl QrDecomposition for DMat3 {
+  /// Computes the QR decomposition of a dense 3x3 matrix
+  fn qr(&self) -> (DMat3, DMat3) {
+    qr3(*self)
+  }
+}
+
+impl QrDecomposition for DMat4 {
+  /// Computes the QR decomposition of a dense 4x4 matrix
+  fn qr(&self) -> (DMat4, DMat4) {
+    qr4(*self)
+  }
+}
+
+pub trait HessenbergDecomposition {
+  // Returns (Q, H) with A = Q H Qᵀ, where Q is orthogonal and H is upper hessenberg
+  // The entries below the subdiagonal of H are exactly zero, and H is tridiagonal for symmetric A
+  fn hessenberg(&self) -> (Self, Self)
+  where
+    Self: Sized;
+}
+
+impl HessenbergDecomposition for DMat2 {
+  /// Computes the hessenberg decomposition of a dense 2x2 matrix, which is already in hessenberg form
+  fn hessenberg(&self) -> (DMat2, DMat2) {
+    (DMat2::IDENTITY, *self)
+  }
+}
+
+impl HessenbergDecomposition for DMat3 {
+  /// Computes the hessenberg decomposition of a dense 3x3 matrix
+  fn hessenberg(&self) -> (DMat3, DMat3) {
+    hessenberg3(*self)
+  }
+}
+
+impl HessenbergDecomposition for DMat4 {
+  /// Computes the hessenberg decomposition of a dense 4x4 matrix
+  fn hessenberg(&self) -> (DMat4, DMat4) {
+    hessenberg4(*self)
+  }
+}
+
+pub trait SchurDecomposition {
+  // Returns (Z, T) with A = Z T Zᵀ, where Z is orthogonal and T is in standardized real Schur form.
+  // T is upper triangular, except for a 2x2 diagonal block [a b; c a] with b * c < 0 for each pair
+  // of complex conjugate eigenvalues a ± i√(-bc)
+  fn schur(&self) -> (Self, Self)
+  where
+    Self: Sized;
+
+  fn try_schur(&self) -> Result<(Self, Self), LinalgError>
+  where
+    Self: Sized;
+}
+
+impl SchurDecomposition for DMat2 {
+  /// Computes the real Schur decomposition of a dense 2x2 matrix
+  fn schur(&self) -> (DMat2, DMat2) {
+    schur2(*self)
+  }
+
+  /// Computes the real Schur decomposition of a dense 2x2 matrix, failing on non-finite input
+  fn try_schur(&self) -> Result<(DMat2, DMat2), LinalgError> {
+    if !self.is_finite() {
+      return Err(LinalgError::NonFiniteInput);
+    }
+    Ok(schur2(*self))
+  }
+}
+
+impl SchurDecomposition for DMat3 {
+  /// Computes the real Schur decomposition of a dense 3x3 matrix
+  fn schur(&self) -> (DMat3, DMat3) {
+    schur3(*self).unwrap_or_else(|e| panic!("{e}"))
+  }
+
+  /// Computes the real Schur decomposition of a dense 3x3 matrix, failing on non-finite input or non-convergence
+  fn try_schur(&self) -> Result<(DMat3, DMat3), LinalgError> {
+    schur3(*self)
+  }
+}
+
+impl SchurDecomposition for DMat4 {
+  /// Computes the real Schur decomposition of a dense 4x4 matrix
+  fn schur(&self) -> (DMat4, DMat4) {
+    schur4(*self).unwrap_or_else(|e| panic!("{e}"))
+  }
+
+  /// Computes the real Schur decomposition of a dense 4x4 matrix, failing on non-finite input or non-convergence
+  fn try_schur(&self) -> Result<(DMat4, DMat4), LinalgError> {
+    schur4(*self)
+  }
+}
+
+pub trait SingularValueDecomposition<const N: usize> {
+  // Returns (U, σ, Vᵀ) with A = U diag(σ) Vᵀ, where U and V are orthogonal and the singular values σ
+  // are non-negative and in descending order
+  fn svd(&self) -> (Self, [f64; N], Self)
+  where
+    Self: Sized;
+
+  fn singular_values(&self) -> [f64; N];
+
+  // Returns the Moore-Penrose pseudo-inverse and the numerical rank, treating the singular values
+  // at most rcond σ_max as zero
+  fn pinv(&self, rcond: f64) -> (Self, usize)
+  where
+    Self: Sized;
+}
+
+impl SingularValueDecomposition<2> for DMat2 {
+  /// Computes the singular value decomposition of a dense 2x2 matrix in closed form
+  fn svd(&self) -> (DMat2, [f64; 2], DMat2) {
+    svd2(*self)
+  }
+
+  /// Computes the singular values of a dense 2x2 matrix in closed form
+  fn singular_values(&self) -> [f64; 2] {
+    singular_values2(*self)
+  }
+
+  /// Computes the pseudo-inverse of a dense 2x2 matrix from its singular value decomposition
+  fn pinv(&self, rcond: f64) -> (DMat2, usize) {
+    pinv2(*self, rcond)
+  }
 }
 
-impl EigenDecomposition<2> for DMat2 {
-  /// Computes the eigenvalues of a dense 2x2 matrix
-  fn eigvals(&self) -> [DVec2; 2] {
-    eigvals2(*self)
+impl SingularValueDecomposition<3> for DMat3 {
+  /// Computes the singular value decomposition of a dense 3x3 matrix
+  fn svd(&self) -> (DMat3, [f64; 3], DMat3) {
+    svd3(*self)
+  }
+
+  /// Computes the singular values of a dense 3x3 matrix
+  fn singular_values(&self) -> [f64; 3] {
+    singular_values3(*self)
   }
 
-  /// Computes the eigenvalues of a dense, symmetric 2x2 matrix
-  fn eigvals_symmetric(&self) -> [f64; 2] {
-    eigvals2_symmetric(*self)
+  /// Computes the pseudo-inverse of a dense 3x3 matrix from its singular value decomposition
+  fn pinv(&self, rcond: f64) -> (DMat3, usize) {
+    pinv3(*self, rcond)
   }
 }
 
-impl EigenDecomposition<3> for DMat3 {
-  /// Computes the eigenvalues of a dense 3x3 matrix
-  fn eigvals(&self) -> [DVec2; 3] {
-    eigvals3_hessenberg(hessenberg3(*self))
+impl SingularValueDecomposition<4> for DMat4 {
+  /// Computes the singular value decomposition of a dense 4x4 matrix
+  fn svd(&self) -> (DMat4, [f64; 4], DMat4) {
+    svd4(*self)
   }
 
-  /// Computes the eigenvalues of a dense, symmetric 3x3 matrix
-  fn eigvals_symmetric(&self) -> [f64; 3] {
-    eigvals3_symmetric(*self)
+  /// Computes the singular values of a dense 4x4 matrix
+  fn singular_values(&self) -> [f64; 4] {
+    singular_values4(*self)
+  }
+
+  /// Computes the pseudo-inverse of a dense 4x4 matrix from its singular value decomposition
+  fn pinv(&self, rcond: f64) -> (DMat4, usize) {
+    pinv4(*self, rcond)
   }
 }
 
-impl EigenDecomposition<4> for DMat4 {
-  /// Computes the eigenvalues of a dense 4x4 matrix
-  fn eigvals(&self) -> [DVec2; 4] {
-    eigvals4_hessenberg(hessenberg4(*self))
+pub trait PolarDecomposition {
+  // Returns (R, S) with A = R S, where R is orthogonal and S is symmetric positive semidefinite
+  fn polar(&self) -> (Self, Self)
+  where
+    Self: Sized;
+
+  // Returns (R, S) with A = R S, where R is a proper rotation (det(R) = +1) and S is symmetric. When A
+  // is a reflection, S has a single negative eigenvalue of the smallest possible magnitude
+  fn polar_rotation(&self) -> (Self, Self)
+  where
+    Self: Sized;
+}
+
+impl PolarDecomposition for DMat2 {
+  /// Computes the polar decomposition of a dense 2x2 matrix
+  fn polar(&self) -> (DMat2, DMat2) {
+    polar2(*self)
+  }
+
+  /// Computes the polar decomposition of a dense 2x2 matrix with a proper rotation
+  fn polar_rotation(&self) -> (DMat2, DMat2) {
+    polar2_rotation(*self)
+  }
+}
+
+impl PolarDecomposition for DMat3 {
+  /// Computes the polar decomposition of a dense 3x3 matrix
+  fn polar(&self) -> (DMat3, DMat3) {
+    polar3(*self)
   }
 
-  /// Computes the eigenvalues of a dense, symmetric 4x4 matrix
-  fn eigvals_symmetric(&self) -> [f64; 4] {
-    eigvals4_symmetric(*self)
+  /// Computes the polar decomposition of a dense 3x3 matrix with a proper rotation
+  fn polar_rotation(&self) -> (DMat3, DMat3) {
+    polar3_rotation(*self)
   }
 }
+
+pub trait LuDecomposition<const N: usize> {
+  // Returns the factors of P A = L U with partial pivoting, which can be reused to solve linear systems
+  fn lu(&self) -> Lu<Self, N>
+  where
+    Self: Sized;
+}
+
+impl LuDecomposition<2> for DMat2 {
+  /// Computes the LU decomposition of a dense 2x2 matrix
+  fn lu(&self) -> Lu<DMat2, 2> {
+    Lu::<DMat2, 2>::new(*self)
+  }
+}
+
+impl LuDecomposition<3> for DMat3 {
+  /// Computes the LU decomposition of a dense 3x3 matrix
+  fn lu(&self) -> Lu<DMat3, 3> {
+    Lu::<DMat3, 3>::new(*self)
+  }
+}
+
+impl LuDecomposition<4> for DMat4 {
+  /// Computes the LU decomposition of a dense 4x4 matrix
+  fn lu(&self) -> Lu<DMat4, 4> {
+    Lu::<DMat4, 4>::new(*self)
+  }
+}
+
+pub trait CholeskyDecomposition<const N: usize> {
+  // Returns the factor of A = L Lᵀ, or None if A is not positive definite. Only the lower triangle
+  // of A is read
+  fn cholesky(&self) -> Option<Cholesky<Self>>
+  where
+    Self: Sized;
+
+  // Returns the factors of P A Pᵀ = L D Lᵀ, which also exist for semidefinite and indefinite A. Only
+  // the lower triangle of A is read
+  fn ldlt(&self) -> Ldlt<Self, N>
+  where
+    Self: Sized;
+}
+
+impl CholeskyDecomposition<2> for DMat2 {
+  /// Computes the Cholesky decomposition of a symmetric 2x2 matrix
+  fn cholesky(&self) -> Option<Cholesky<DMat2>> {
+    Cholesky::<DMat2>::new(*self)
+  }
+
+  /// Computes the LDLᵀ decomposition of a symmetric 2x2 matrix
+  fn ldlt(&self) -> Ldlt<DMat2, 2> {
+    Ldlt::<DMat2, 2>::new(*self)
+  }
+}
+
+impl CholeskyDecomposition<3> for DMat3 {
+  /// Computes the Cholesky decomposition of a symmetric 3x3 matrix
+  fn cholesky(&self) -> Option<Cholesky<DMat3>> {
+    Cholesky::<DMat3>::new(*self)
+  }
+
+  /// Computes the LDLᵀ decomposition of a symmetric 3x3 matrix
+  fn ldlt(&self) -> Ldlt<DMat3, 3> {
+    Ldlt::<DMat3, 3>::new(*self)
+  }
+}
+
+impl CholeskyDecomposition<4> for DMat4 {
+  /// Computes the Cholesky decomposition of a symmetric 4x4 matrix
+  fn cholesky(&self) -> Option<Cholesky<DMat4>> {
+    Cholesky::<DMat4>::new(*self)
+  }
+
+  /// Computes the LDLᵀ decomposition of a symmetric 4x4 matrix
+  fn ldlt(&self) -> Ldlt<DMat4, 4> {
+    Ldlt::<DMat4, 4>::new(*self)
+  }
+}
+
+pub trait MatrixFunctions {
+  // The matrix exponential, given by the power series Σ Aᵏ / k!, which is not finite for non-finite input
+  fn exp(&self) -> Self
+  where
+    Self: Sized;
+
+  // The principal logarithm, the unique logarithm whose eigenvalues have imaginary parts in (-π, π).
+  // It exists when no eigenvalue lies on the closed negative real axis
+  fn log(&self) -> Result<Self, LinalgError>
+  where
+    Self: Sized;
+
+  // The principal square root, the unique square root whose eigenvalues have positive real parts.
+  // It exists when no eigenvalue lies on the closed negative real axis
+  fn sqrt(&self) -> Result<Self, LinalgError>
+  where
+    Self: Sized;
+}
+
+impl MatrixFunctions for DMat2 {
+  /// Computes the exponential of a dense 2x2 matrix in closed form
+  fn exp(&self) -> DMat2 {
+    exp2(*self)
+  }
+
+  /// Computes the principal logarithm of a dense 2x2 matrix
+  fn log(&self) -> Result<DMat2, LinalgError> {
+    log2(*self)
+  }
+
+  /// Computes the principal square root of a dense 2x2 matrix
+  fn sqrt(&self) -> Result<DMat2, LinalgError> {
+    sqrt2(*self)
+  }
+}
+
+impl MatrixFunctions for DMat3 {
+  /// Computes the exponential of a dense 3x3 matrix
+  fn exp(&self) -> DMat3 {
+    exp3(*self)
+  }
+
+  /// Computes the principal logarithm of a dense 3x3 matrix
+  fn log(&self) -> Result<DMat3, LinalgError> {
+    log3(*self)
+  }
+
+  /// Computes the principal square root of a dense 3x3 matrix
+  fn sqrt(&self) -> Result<DMat3, LinalgError> {
+    sqrt3(*self)
+  }
+}
+
+impl MatrixFunctions for DMat4 {
+  /// Computes the exponential of a dense 4x4 matrix
+  fn exp(&self) -> DMat4 {
+    exp4(*self)
+  }
+
+  /// Computes the principal logarithm of a dense 4x4 matrix
+  fn log(&self) -> Result<DMat4, LinalgError> {
+    log4(*self)
+  }
+
+  /// Computes the principal square root of a dense 4x4 matrix
+  fn sqrt(&self) -> Result<DMat4, LinalgError> {
+    sqrt4(*self)
+  }
+}
+
+pub trait SpectralFunctions {
+  // Applies f to the eigenvalues of a symmetric matrix A = V diag(λ) Vᵀ, returning V diag(f(λ)) Vᵀ
+  fn map_symmetric(&self, f: impl Fn(f64) -> f64) -> Self;
+
+  // The square root of a symmetric positive semidefinite matrix
+  fn sqrt_spd(&self) -> Self
+  where
+    Self: Sized,
+  {
+    self.map_symmetric(f64::sqrt)
+  }
+
+  // The inverse square root of a symmetric positive definite matrix
+  fn inv_sqrt_spd(&self) -> Self
+  where
+    Self: Sized,
+  {
+    self.map_symmetric(|lambda| lambda.sqrt().recip())
+  }
+
+  // The logarithm of a symmetric positive definite matrix
+  fn log_spd(&self) -> Self
+  where
+    Self: Sized,
+  {
+    self.map_symmetric(f64::ln)
+  }
+
+  // The real power of a symmetric positive definite matrix
+  fn powf_spd(&self, p: f64) -> Self
+  where
+    Self: Sized,
+  {
+    self.map_symmetric(|lambda| lambda.powf(p))
+  }
+
+  // The closest symmetric positive semidefinite matrix in the Frobenius norm, clamping the negative
+  // eigenvalues to zero
+  fn project_psd(&self) -> Self
+  where
+    Self: Sized,
+  {
+    self.map_symmetric(|lambda| lambda.max(0.0))
+  }
+}
+
+macro_rules! impl_spectral_functions {
+  ($Mat:ty, $Vec:ty) => {
+    impl SpectralFunctions for $Mat {
+      /// Applies a function to the eigenvalues of a dense, symmetric matrix
+      fn map_symmetric(&self, f: impl Fn(f64) -> f64) -> $Mat {
+        let (eigvals, V) = self.eig_symmetric();
+        let M = V * <$Mat>::from_diagonal(<$Vec>::from(eigvals.map(f))) * V.transpose();
+        // Symmetrize exactly, as the product is only symmetric up to rounding
+        (M + M.transpose()) * 0.5
+      }
+    }
+  };
+}
+
+impl_spectral_functions!(DMat2, DVec2);
+impl_spectral_functions!(DMat3, DVec3);
+impl_spectral_functions!(DMat4, DVec4);
@@ -0,0 +1,174 @@
+use crate::norms::norm_max;
+use crate::utils::Real;
+
+use glam::{DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+
+/// The LU decomposition P A = L U of a square matrix with partial pivoting
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lu<M, const N: usize> {
+  /// The unit lower triangular factor
+  pub L: M,
+  /// The upper triangular factor
+  pub U: M,
+  /// The row permutation, where row i of P A is row perm[i] of A
+  pub perm: [usize; N],
+  /// Whether a pivot of U is zero relative to the magnitude of A, or A is not finite
+  pub singular: bool,
+}
+
+/// Computes the LU decomposition of a square matrix given as an array of columns
+// The pivot in each column is its largest remaining entry, which keeps the entries of L at most 1 in
+// magnitude. The matrix is flagged as singular if a pivot falls below N ε max|A|, or if the input or a
+// pivot is not finite, as the comparison with the tolerance does not hold for NaN
+pub(crate) fn lu<T: Real, const N: usize>(mut A: [[T; N]; N]) -> Lu<[[T; N]; N], N> {
+  let max = norm_max(&A);
+  let tol = T::from_f64(N as f64) * T::EPSILON * max;
+
+  let mut perm: [usize; N] = std::array::from_fn(|i| i);
+  let mut L = [[T::ZERO; N]; N];
+  let mut singular = !max.is_finite();
+
+  for k in 0..N {
+    let p = (k..N)
+      .max_by(|&i, &j| A[k][i].abs().total_cmp(&A[k][j].abs()))
+      .unwrap();
+
+    if p != k {
+      perm.swap(p, k);
+      A.iter_mut().for_each(|col| col.swap(p, k));
+      L.iter_mut().for_each(|col| col.swap(p, k));
+    }
+
+    let pivot = A[k][k];
+    singular |= pivot.abs() <= tol || !pivot.is_finite();
+
+    L[k][k] = T::ONE;
+    if pivot == T::ZERO {
+      continue;
+    }
+
+    let (head, tail) = A.split_at_mut(k + 1);
+    for i in k + 1..N {
+      L[k][i] = head[k][i] / pivot;
+      head[k][i] = T::ZERO;
+    }
+    for col in tail {
+      for i in k + 1..N {
+        col[i] -= L[k][i] * col[k];
+      }
+    }
+  }
+
+  Lu {
+    L,
+    U: A,
+    perm,
+    singular,
+  }
+}
+
+/// Solves L U x = P b by forward and back substitution
+pub(crate) fn lu_solve<T: Real, const N: usize>(
+  L: &[[T; N]; N],
+  U: &[[T; N]; N],
+  perm: &[usize; N],
+  b: [T; N],
+) -> [T; N] {
+  let mut x = perm.map(|i| b[i]);
+
+  for j in 0..N {
+    for i in j + 1..N {
+      x[i] -= L[j][i] * x[j];
+    }
+  }
+
+  for j in (0..N).rev() {
+    x[j] /= U[j][j];
+    for i in 0..j {
+      x[i] -= U[j][i] * x[j];
+    }
+  }
+
+  x
+}
+
+macro_rules! impl_lu {
+  ($num:expr, $Mat:ty, $Vec:ty) => {
+    impl Lu<$Mat, $num> {
+      pub(crate) fn new(A: $Mat) -> Self {
+        let Lu {
+          L,
+          U,
+          perm,
+          singular,
+        } = lu(A.to_cols_array_2d());
+        Self {
+          L: <$Mat>::from_cols_array_2d(&L),
+          U: <$Mat>::from_cols_array_2d(&U),
+          perm,
+          singular,
+        }
+      }
+
+      /// Solves A x = b, or returns None if A is singular
+      pub fn solve(&self, b: $Vec) -> Option<$Vec> {
+        if self.singular {
+          return None;
+        }
+
+        let L = self.L.to_cols_array_2d();
+        let U = self.U.to_cols_array_2d();
+        Some(lu_solve(&L, &U, &self.perm, b.into()).into())
+      }
+
+      /// Solves A X = B for each column of B, or returns None if A is singular
+      pub fn solve_mat(&self, B: $Mat) -> Option<$Mat> {
+        if self.singular {
+          return None;
+        }
+
+        let L = self.L.to_cols_array_2d();
+        let U = self.U.to_cols_array_2d();
+        let X = B
+          .to_cols_array_2d()
+          .map(|b| lu_solve(&L, &U, &self.perm, b));
+        Some(<$Mat>::from_cols_array_2d(&X))
+      }
+
+      /// Returns the permutation matrix P with P A = L U
+      pub fn permutation(&self) -> $Mat {
+        let mut P = [[0.0; $num]; $num];
+        for (i, &j) in self.perm.iter().enumerate() {
+          P[j][i] = 1.0;
+        }
+        <$Mat>::from_cols_array_2d(&P)
+      }
+
+      /// Computes the determinant of A from the pivots
+      pub fn determinant(&self) -> f64 {
+        let U = self.U.to_cols_array_2d();
+        let sign = if permutation_parity(&self.perm) {
+          -1.0
+        } else {
+          1.0
+        };
+        sign * (0..$num).map(|i| U[i][i]).product::<f64>()
+      }
+    }
+  };
+}
+
+// Returns true if the permutation is odd
+fn permutation_parity<const N: usize>(perm: &[usize; N]) -> bool {
+  let mut odd = false;
+  for i in 0..N {
+    for j in i + 1..N {
+      odd ^= perm[i] > perm[j];
+    }
+  }
+  odd
+}
+
+impl_lu!(2, DMat2, DVec2);
+impl_lu!(3, DMat3, DVec3);
+impl_lu!(4, DMat4, DVec4);
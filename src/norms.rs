@@ -0,0 +1,78 @@
+use crate::lu::*;
+use crate::utils::Real;
+
+// The matrices here are given as arrays of columns
+
+// Unlike max, this keeps a NaN operand, so that a matrix with a NaN entry does not get a finite norm
+fn max_nan<T: Real>(max: T, x: T) -> T {
+  if x.is_nan() || x > max {
+    x
+  } else {
+    max
+  }
+}
+
+/// Computes the maximum absolute column sum
+pub(crate) fn norm1<T: Real, const N: usize>(A: &[[T; N]; N]) -> T {
+  A.iter()
+    .map(|col| col.iter().map(|x| x.abs()).sum())
+    .fold(T::ZERO, max_nan)
+}
+
+/// Computes the maximum absolute row sum
+pub(crate) fn norm_inf<T: Real, const N: usize>(A: &[[T; N]; N]) -> T {
+  (0..N)
+    .map(|i| A.iter().map(|col| col[i].abs()).sum())
+    .fold(T::ZERO, max_nan)
+}
+
+/// Computes the square root of the sum of the squared entries
+// The entries are scaled by the largest magnitude first, so that the squares cannot overflow or underflow
+pub(crate) fn norm_frobenius<T: Real, const N: usize>(A: &[[T; N]; N]) -> T {
+  let max = norm_max(A);
+  if max == T::ZERO || !max.is_finite() {
+    return max;
+  }
+  max
+    * A
+      .iter()
+      .flatten()
+      .map(|&x| (x / max) * (x / max))
+      .sum::<T>()
+      .sqrt()
+}
+
+/// Computes the largest absolute entry
+pub(crate) fn norm_max<T: Real, const N: usize>(A: &[[T; N]; N]) -> T {
+  A.iter()
+    .flatten()
+    .fold(T::ZERO, |max, x| max_nan(max, x.abs()))
+}
+
+/// Computes the condition number ‖A‖₁ ‖A⁻¹‖₁, which is infinite for singular matrices
+// As with cond2, only an exactly zero pivot counts as singular, while nearly singular matrices give large
+// but finite values. Non-finite input has no meaningful condition number, so it gives NaN
+pub(crate) fn cond1<T: Real, const N: usize>(A: &[[T; N]; N]) -> T {
+  if !A.iter().flatten().all(|x| x.is_finite()) {
+    return T::NAN;
+  }
+
+  let Lu { L, U, perm, .. } = lu(*A);
+  if (0..N).any(|k| U[k][k] == T::ZERO) {
+    return T::INFINITY;
+  }
+
+  let A_inv: [[T; N]; N] = std::array::from_fn(|k| {
+    let e = std::array::from_fn(|i| if i == k { T::ONE } else { T::ZERO });
+    lu_solve(&L, &U, &perm, e)
+  });
+  norm1(A) * norm1(&A_inv)
+}
+
+/// Computes the condition number σ_max / σ_min from the singular values in descending order
+pub(crate) fn cond2<T: Real, const N: usize>(sigma: &[T; N]) -> T {
+  if sigma[N - 1] == T::ZERO {
+    return T::INFINITY;
+  }
+  sigma[0] / sigma[N - 1]
+}
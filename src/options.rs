@@ -0,0 +1,90 @@
+use crate::utils::Real;
+
+/// Options for the QR algorithm behind the general eigenvalue methods
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EigenOptions {
+  /// A subdiagonal entry is deflated once its magnitude is at most this multiple of the sum of the
+  /// magnitudes of the neighbouring diagonal entries. It is raised to the machine epsilon of the scalar
+  /// type where it falls below, so that the default acts as f32::EPSILON for the f32 matrices
+  pub tolerance: f64,
+  /// The number of iterations after which the algorithm gives up, counted separately for each block
+  /// left over after a deflation
+  pub max_iterations: usize,
+  /// Whether to replace the shift every few iterations without deflation, which breaks up cycles that
+  /// the standard shift cannot escape
+  pub exceptional_shifts: bool,
+}
+
+impl Default for EigenOptions {
+  fn default() -> Self {
+    Self {
+      tolerance: 1e-14,
+      max_iterations: 100,
+      exceptional_shifts: true,
+    }
+  }
+}
+
+impl EigenOptions {
+  #[inline]
+  pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+    self.tolerance = tolerance;
+    self
+  }
+
+  #[inline]
+  pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+    self.max_iterations = max_iterations;
+    self
+  }
+
+  #[inline]
+  pub fn with_exceptional_shifts(mut self, exceptional_shifts: bool) -> Self {
+    self.exceptional_shifts = exceptional_shifts;
+    self
+  }
+
+  // Exceptional shifts are taken every 10 iterations without deflation, as in LAPACK's dlahqr
+  pub(crate) fn is_exceptional(&self, iteration: usize) -> bool {
+    self.exceptional_shifts && iteration > 0 && iteration.is_multiple_of(10)
+  }
+
+  // Rounding leaves the subdiagonal entries at around the machine epsilon relative to the diagonal, so that
+  // a smaller tolerance could never be met
+  pub(crate) fn is_negligible<T: Real>(&self, subdiagonal: T, a: T, b: T) -> bool {
+    let tolerance = T::from_f64(self.tolerance).max(T::EPSILON);
+    subdiagonal.abs() <= tolerance * (a.abs() + b.abs())
+  }
+}
+
+/// Diagnostics of a run of the QR algorithm on an NxN matrix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EigenDiagnostics<const N: usize> {
+  /// The total number of QR iterations over all blocks
+  pub iterations: usize,
+  subdiagonal: [f64; N],
+}
+
+impl<const N: usize> EigenDiagnostics<N> {
+  pub(crate) fn new(iterations: usize, subdiagonal: &[f64]) -> Self {
+    let mut diagnostics = Self {
+      iterations,
+      subdiagonal: [0.0; N],
+    };
+    diagnostics.subdiagonal[..N - 1].copy_from_slice(subdiagonal);
+    diagnostics
+  }
+
+  // Expresses the subdiagonal magnitudes of a normalized matrix in the units of the original one
+  pub(crate) fn scaled(mut self, scale: f64) -> Self {
+    self.subdiagonal.iter_mut().for_each(|x| *x *= scale);
+    self
+  }
+
+  /// The magnitudes of the N - 1 subdiagonal entries of the final iterate
+  // Deflated entries are below the tolerance, while the others belong to 2x2 blocks that were solved directly
+  #[inline]
+  pub fn subdiagonal(&self) -> &[f64] {
+    &self.subdiagonal[..N - 1]
+  }
+}
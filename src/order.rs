@@ -0,0 +1,61 @@
+use crate::utils::Real;
+use crate::Complex;
+
+use std::cmp::Ordering;
+
+/// The order in which eigenvalues are returned
+// Complex eigenvalues are compared by real part, then by the magnitude of the imaginary part, so that each
+// conjugate pair stays adjacent
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EigenOrder {
+  /// Increasing value, which is the order of the methods without an explicit order.
+  /// Each conjugate pair has its negative imaginary part first
+  #[default]
+  Ascending,
+  /// Decreasing value, the reverse of Ascending.
+  /// Each conjugate pair has its positive imaginary part first
+  Descending,
+  /// Decreasing absolute value, with ties in ascending order.
+  /// Each conjugate pair has its negative imaginary part first
+  LargestMagnitude,
+}
+
+pub(crate) fn cmp_complex<T: Real>(a: &Complex<T>, b: &Complex<T>) -> Ordering {
+  a.re
+    .total_cmp(&b.re)
+    .then(a.im.abs().total_cmp(&b.im.abs()))
+    .then(a.im.total_cmp(&b.im))
+}
+
+impl EigenOrder {
+  // The permutation taking the eigenvalues into this order, stable for equal eigenvalues
+  pub(crate) fn argsort<T: Real, const N: usize>(self, eigvals: &[T; N]) -> [usize; N] {
+    let mut perm: [usize; N] = std::array::from_fn(|i| i);
+    let (x, cmp) = (eigvals, T::total_cmp);
+    match self {
+      Self::Ascending => perm.sort_by(|&i, &j| cmp(&x[i], &x[j])),
+      Self::Descending => perm.sort_by(|&i, &j| cmp(&x[j], &x[i])),
+      Self::LargestMagnitude => {
+        perm.sort_by(|&i, &j| cmp(&x[j].abs(), &x[i].abs()).then(cmp(&x[i], &x[j])))
+      }
+    }
+    perm
+  }
+
+  pub(crate) fn argsort_complex<T: Real, const N: usize>(
+    self,
+    eigvals: &[Complex<T>; N],
+  ) -> [usize; N] {
+    let mut perm: [usize; N] = std::array::from_fn(|i| i);
+    let (x, cmp) = (eigvals, cmp_complex);
+    let abs = |z: &Complex<T>| z.re.hypot(z.im);
+    match self {
+      Self::Ascending => perm.sort_by(|&i, &j| cmp(&x[i], &x[j])),
+      Self::Descending => perm.sort_by(|&i, &j| cmp(&x[j], &x[i])),
+      Self::LargestMagnitude => {
+        perm.sort_by(|&i, &j| abs(&x[j]).total_cmp(&abs(&x[i])).then(cmp(&x[i], &x[j])))
+      }
+    }
+    perm
+  }
+}
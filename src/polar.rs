@@ -0,0 +1,50 @@
+use crate::svd::*;
+
+use glam::{DMat2, DMat3, DVec2, DVec3};
+
+/// Computes the polar decomposition A = R S of a dense 2x2 matrix
+// With A = U Σ Vᵀ, the orthogonal factor is R = U Vᵀ and the stretch is S = V Σ Vᵀ
+pub(crate) fn polar2(A: DMat2) -> (DMat2, DMat2) {
+  let (U, sigma, Vt) = svd2(A);
+  polar_from_svd2(U, DVec2::from(sigma), Vt)
+}
+
+/// Computes the polar decomposition A = R S of a dense 2x2 matrix where R is a proper rotation
+// If U Vᵀ is a reflection, the smallest singular value and its left singular vector are negated,
+// which gives the rotation closest to A. S is then symmetric but indefinite
+pub(crate) fn polar2_rotation(A: DMat2) -> (DMat2, DMat2) {
+  let (mut U, sigma, Vt) = svd2(A);
+  let mut sigma = DVec2::from(sigma);
+  if U.determinant() * Vt.determinant() < 0.0 {
+    U.y_axis = -U.y_axis;
+    sigma.y = -sigma.y;
+  }
+  polar_from_svd2(U, sigma, Vt)
+}
+
+fn polar_from_svd2(U: DMat2, sigma: DVec2, Vt: DMat2) -> (DMat2, DMat2) {
+  let S = Vt.transpose() * DMat2::from_diagonal(sigma) * Vt;
+  (U * Vt, 0.5 * (S + S.transpose()))
+}
+
+/// Computes the polar decomposition A = R S of a dense 3x3 matrix
+pub(crate) fn polar3(A: DMat3) -> (DMat3, DMat3) {
+  let (U, sigma, Vt) = svd3(A);
+  polar_from_svd3(U, DVec3::from(sigma), Vt)
+}
+
+/// Computes the polar decomposition A = R S of a dense 3x3 matrix where R is a proper rotation
+pub(crate) fn polar3_rotation(A: DMat3) -> (DMat3, DMat3) {
+  let (mut U, sigma, Vt) = svd3(A);
+  let mut sigma = DVec3::from(sigma);
+  if U.determinant() * Vt.determinant() < 0.0 {
+    U.z_axis = -U.z_axis;
+    sigma.z = -sigma.z;
+  }
+  polar_from_svd3(U, sigma, Vt)
+}
+
+fn polar_from_svd3(U: DMat3, sigma: DVec3, Vt: DMat3) -> (DMat3, DMat3) {
+  let S = Vt.transpose() * DMat3::from_diagonal(sigma) * Vt;
+  (U * Vt, 0.5 * (S + S.transpose()))
+}
@@ -0,0 +1,24 @@
+use crate::utils::Real;
+
+// The matrices here are given as arrays of columns, and the entries are compared with an absolute tolerance
+
+pub(crate) fn is_symmetric<T: Real, const N: usize>(A: &[[T; N]; N], tol: T) -> bool {
+  (0..N).all(|j| (j + 1..N).all(|i| (A[j][i] - A[i][j]).abs() <= tol))
+}
+
+pub(crate) fn is_skew_symmetric<T: Real, const N: usize>(A: &[[T; N]; N], tol: T) -> bool {
+  (0..N).all(|j| (j..N).all(|i| (A[j][i] + A[i][j]).abs() <= tol))
+}
+
+/// Checks that the entries more than k places below the diagonal vanish
+pub(crate) fn is_lower_bandwidth<T: Real, const N: usize>(
+  A: &[[T; N]; N],
+  k: usize,
+  tol: T,
+) -> bool {
+  (0..N).all(|j| (j + k + 1..N).all(|i| A[j][i].abs() <= tol))
+}
+
+pub(crate) fn is_diagonal<T: Real, const N: usize>(A: &[[T; N]; N], tol: T) -> bool {
+  (0..N).all(|j| (0..N).all(|i| i == j || A[j][i].abs() <= tol))
+}
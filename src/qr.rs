@@ -0,0 +1,106 @@
+use crate::utils::*;
+
+use glam::{
+  dvec2, dvec3, dvec4, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, Vec3Swizzles, Vec4Swizzles,
+};
+
+// These householder functions return the reflection mapping a onto the first axis,
+// or the identity if a already lies on it
+// The reflection only depends on the direction of a, which is first scaled as in normalization
+pub(crate) fn householder2(a: DVec2) -> DMat2 {
+  if a.y == 0.0 {
+    return DMat2::IDENTITY;
+  }
+
+  let a = a / a.abs().max_element();
+  let d = a.x + a.length().copysign(a.x);
+  let v = dvec2(1.0, a.y / d).normalize();
+  DMat2::IDENTITY - 2.0 * outer_product2(v, v)
+}
+
+pub(crate) fn householder3(a: DVec3) -> DMat3 {
+  if a.yz() == DVec2::ZERO {
+    return DMat3::IDENTITY;
+  }
+
+  let a = a / a.abs().max_element();
+  let d = a.x + a.length().copysign(a.x);
+  let v = dvec3(1.0, a.y / d, a.z / d).normalize();
+  DMat3::IDENTITY - 2.0 * outer_product3(v, v)
+}
+
+pub(crate) fn householder4(a: DVec4) -> DMat4 {
+  if a.yzw() == DVec3::ZERO {
+    return DMat4::IDENTITY;
+  }
+
+  let a = a / a.abs().max_element();
+  let d = a.x + a.length().copysign(a.x);
+  let v = dvec4(1.0, a.y / d, a.z / d, a.w / d).normalize();
+  DMat4::IDENTITY - 2.0 * outer_product4(v, v)
+}
+
+/// Computes a QR decomposition of a dense 2x2 matrix
+pub(crate) fn qr2(A: DMat2) -> (DMat2, DMat2) {
+  let Q = householder2(A.x_axis);
+  let mut R = Q * A;
+
+  R.x_axis.y = 0.0;
+
+  (Q, R)
+}
+
+/// Computes a QR decomposition of a dense 3x3 matrix
+// The entries below the diagonal of R are zero up to rounding, and are cleared explicitly
+pub(crate) fn qr3(A: DMat3) -> (DMat3, DMat3) {
+  let mut A = A;
+  let mut Q = DMat3::IDENTITY;
+
+  let H = householder3(A.x_axis);
+  Q *= H;
+  A = H * A;
+
+  let H = householder2(A.y_axis.yz());
+  let H = DMat3::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, 0.0, H.x_axis.y, H.y_axis.y,
+  ]);
+  Q *= H;
+  A = H * A;
+
+  A.x_axis = dvec3(A.x_axis.x, 0.0, 0.0);
+  A.y_axis.z = 0.0;
+
+  (Q, A)
+}
+
+/// Computes a QR decomposition of a dense 4x4 matrix
+pub(crate) fn qr4(A: DMat4) -> (DMat4, DMat4) {
+  let mut A = A;
+  let mut Q = DMat4::IDENTITY;
+
+  let H = householder4(A.x_axis);
+  Q *= H;
+  A = H * A;
+
+  let H = householder3(A.y_axis.yzw());
+  let H = DMat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, H.z_axis.x, 0.0, H.x_axis.y, H.y_axis.y,
+    H.z_axis.y, 0.0, H.x_axis.z, H.y_axis.z, H.z_axis.z,
+  ]);
+  Q *= H;
+  A = H * A;
+
+  let H = householder2(A.z_axis.zw());
+  let H = DMat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, H.x_axis.x, H.y_axis.x, 0.0, 0.0, H.x_axis.y,
+    H.y_axis.y,
+  ]);
+  Q *= H;
+  A = H * A;
+
+  A.x_axis = dvec4(A.x_axis.x, 0.0, 0.0, 0.0);
+  A.y_axis = dvec4(A.y_axis.x, A.y_axis.y, 0.0, 0.0);
+  A.z_axis.w = 0.0;
+
+  (Q, A)
+}
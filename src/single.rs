@@ -0,0 +1,83 @@
+// The f32 instantiations of the algorithms on glam types, and the EigenDecomposition implementations of the
+// f32 matrices built on them. The names match the f64 functions, so that the same macros serve both
+
+use crate::eigen::{diagonal_block, francis_step, impl_eigen, shifts};
+use crate::eigen_symmetric::{impl_eigen_symmetric, jacobi, tridiagonal_ql};
+use crate::norms::norm_max;
+use crate::utils::{complex, impl_glam_utils, normalization, Real};
+use crate::{glam_assert, glam_assert_symmetric, impl_eigen_ordered};
+use crate::{impl_eigen_decomposition2, impl_eigen_decomposition3, impl_eigen_decomposition4};
+use crate::{
+  Complex32, EigenDecomposition, EigenDiagnostics, EigenOptions, EigenOrder, Eigenvalue,
+  LinalgError, MatExt, Spectrum,
+};
+
+use glam::{
+  vec2, vec3, vec4, Mat2, Mat3, Mat3A, Mat4, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles,
+};
+
+impl_glam_utils!(Mat2, Mat3, Mat4, Vec2, Vec3, Vec4, vec2, vec3, vec4);
+impl_eigen!(f32, Complex32, Mat2, Mat3, Mat4, vec2, vec3, vec4);
+impl_eigen_symmetric!(f32, Mat2, Mat3, Mat4, Vec3, vec2);
+
+impl_eigen_decomposition2!(f32, Complex32, Mat2);
+impl_eigen_decomposition3!(f32, Complex32, Mat3);
+impl_eigen_decomposition4!(f32, Complex32, Mat4);
+
+// Mat3A shares the algorithms of Mat3, as the conversions between them are exact
+impl EigenDecomposition<3> for Mat3A {
+  type Real = f32;
+  type Complex = Complex32;
+
+  /// Computes the eigenvalues of a dense 3x3 matrix
+  fn eigvals(&self) -> [Complex32; 3] {
+    Mat3::from(*self).eigvals()
+  }
+
+  /// Computes the eigenvalues of a dense 3x3 matrix, classified into real eigenvalues and conjugate pairs
+  fn spectrum(&self) -> Spectrum<f32, 3> {
+    Mat3::from(*self).spectrum()
+  }
+
+  /// Computes the eigenvalues and unit eigenvectors of a dense 3x3 matrix
+  fn eig(&self) -> ([Complex32; 3], Mat3A, Mat3A) {
+    let (eigvals, re, im) = Mat3::from(*self).eig();
+    (eigvals, re.into(), im.into())
+  }
+
+  /// Computes the eigenvalues of a dense 3x3 matrix, failing on non-finite input or non-convergence
+  fn try_eigvals(&self) -> Result<[Complex32; 3], LinalgError> {
+    Mat3::from(*self).try_eigvals()
+  }
+
+  /// Computes the spectrum of a dense 3x3 matrix, failing on non-finite input or non-convergence
+  fn try_spectrum(&self) -> Result<Spectrum<f32, 3>, LinalgError> {
+    Mat3::from(*self).try_spectrum()
+  }
+
+  /// Computes the eigenvalues of a dense 3x3 matrix with the given options, along with diagnostics
+  fn eigvals_with(
+    &self,
+    options: &EigenOptions,
+  ) -> Result<([Complex32; 3], EigenDiagnostics<3>), LinalgError> {
+    Mat3::from(*self).eigvals_with(options)
+  }
+
+  /// Computes the eigenvalues of a dense, symmetric 3x3 matrix
+  fn eigvals_symmetric(&self) -> [f32; 3] {
+    Mat3::from(*self).eigvals_symmetric()
+  }
+
+  /// Computes the eigenvalues of a dense, symmetric 3x3 matrix, failing on non-finite input or non-convergence
+  fn try_eigvals_symmetric(&self) -> Result<[f32; 3], LinalgError> {
+    Mat3::from(*self).try_eigvals_symmetric()
+  }
+
+  /// Computes the eigenvalues and orthonormal eigenvectors of a dense, symmetric 3x3 matrix
+  fn eig_symmetric(&self) -> ([f32; 3], Mat3A) {
+    let (eigvals, V) = Mat3::from(*self).eig_symmetric();
+    (eigvals, V.into())
+  }
+
+  impl_eigen_ordered!(3, f32, Complex32, Mat3A);
+}
@@ -0,0 +1,169 @@
+use crate::order::cmp_complex;
+use crate::utils::Real;
+use crate::Complex;
+
+use std::ops::Neg;
+
+/// An eigenvalue of a real matrix, where complex eigenvalues come in conjugate pairs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Eigenvalue<T> {
+  /// A real eigenvalue
+  Real(T),
+  /// The pair of complex eigenvalues re ± i im, with im > 0
+  ConjugatePair { re: T, im: T },
+}
+
+impl<T: Copy + Default + Neg<Output = T>> Eigenvalue<T> {
+  #[inline]
+  pub fn is_real(&self) -> bool {
+    matches!(self, Self::Real(_))
+  }
+
+  /// The number of eigenvalues represented, which is 2 for a conjugate pair
+  #[inline]
+  pub fn multiplicity(&self) -> usize {
+    match self {
+      Self::Real(_) => 1,
+      Self::ConjugatePair { .. } => 2,
+    }
+  }
+
+  /// Returns the eigenvalue as a complex number, taking the member of a pair with positive imaginary part
+  #[inline]
+  pub fn to_complex(self) -> Complex<T> {
+    match self {
+      Self::Real(x) => Complex::new(x, T::default()),
+      Self::ConjugatePair { re, im } => Complex::new(re, im),
+    }
+  }
+
+  #[inline]
+  pub(crate) fn map<U>(self, f: impl Fn(T) -> U) -> Eigenvalue<U> {
+    match self {
+      Self::Real(x) => Eigenvalue::Real(f(x)),
+      Self::ConjugatePair { re, im } => Eigenvalue::ConjugatePair {
+        re: f(re),
+        im: f(im),
+      },
+    }
+  }
+}
+
+/// The eigenvalues of a real NxN matrix, with each conjugate pair stored once
+// The entries are in ascending order of real part, as in EigenOrder::Ascending
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spectrum<T, const N: usize> {
+  values: [Eigenvalue<T>; N],
+  len: usize,
+}
+
+impl<T: Copy + Default + Neg<Output = T>, const N: usize> Spectrum<T, N> {
+  pub(crate) fn new() -> Self {
+    Self {
+      values: [Eigenvalue::Real(T::default()); N],
+      len: 0,
+    }
+  }
+
+  // A spectrum of real eigenvalues, given in ascending order
+  pub(crate) fn from_real(values: [T; N]) -> Self {
+    Self {
+      values: values.map(Eigenvalue::Real),
+      len: N,
+    }
+  }
+
+  pub(crate) fn push(&mut self, value: Eigenvalue<T>) {
+    self.values[self.len] = value;
+    self.len += 1;
+  }
+
+  // Appends the spectrum of the next diagonal block of the Schur form
+  pub(crate) fn extend<const M: usize>(&mut self, other: Spectrum<T, M>) {
+    for &value in other.iter() {
+      self.push(value);
+    }
+  }
+
+  pub(crate) fn sorted(mut self) -> Self
+  where
+    T: Real,
+  {
+    self.values[..self.len].sort_by(|a, b| cmp_complex(&a.to_complex(), &b.to_complex()));
+    self
+  }
+
+  pub(crate) fn map<U: Copy + Default + Neg<Output = U>>(
+    self,
+    f: impl Fn(T) -> U,
+  ) -> Spectrum<U, N> {
+    let mut spectrum = Spectrum::new();
+    for &value in self.iter() {
+      spectrum.push(value.map(&f));
+    }
+    spectrum
+  }
+
+  /// The eigenvalues, with each conjugate pair appearing as a single entry
+  #[inline]
+  pub fn as_slice(&self) -> &[Eigenvalue<T>] {
+    &self.values[..self.len]
+  }
+
+  #[inline]
+  pub fn iter(&self) -> std::slice::Iter<'_, Eigenvalue<T>> {
+    self.as_slice().iter()
+  }
+
+  /// Iterates over the real eigenvalues
+  pub fn real(&self) -> impl Iterator<Item = T> + '_ {
+    self.iter().filter_map(|value| match *value {
+      Eigenvalue::Real(x) => Some(x),
+      Eigenvalue::ConjugatePair { .. } => None,
+    })
+  }
+
+  /// Iterates over the conjugate pairs, each given by its member with positive imaginary part
+  pub fn conjugate_pairs(&self) -> impl Iterator<Item = Complex<T>> + '_ {
+    self.iter().filter_map(|value| match *value {
+      Eigenvalue::Real(_) => None,
+      Eigenvalue::ConjugatePair { re, im } => Some(Complex::new(re, im)),
+    })
+  }
+
+  /// Returns true if all eigenvalues are real
+  #[inline]
+  pub fn is_real(&self) -> bool {
+    self.iter().all(Eigenvalue::is_real)
+  }
+
+  /// Lists all N eigenvalues as complex numbers, with each conjugate pair expanded to re - i im, re + i im
+  pub fn to_complex(&self) -> [Complex<T>; N] {
+    let mut eigvals = [Complex::new(T::default(), T::default()); N];
+    let mut i = 0;
+    for value in self.iter() {
+      match *value {
+        Eigenvalue::Real(x) => {
+          eigvals[i] = Complex::new(x, T::default());
+          i += 1;
+        }
+        Eigenvalue::ConjugatePair { re, im } => {
+          eigvals[i] = Complex::new(re, -im);
+          eigvals[i + 1] = Complex::new(re, im);
+          i += 2;
+        }
+      }
+    }
+    eigvals
+  }
+}
+
+impl<'a, T: Copy + Default + Neg<Output = T>, const N: usize> IntoIterator for &'a Spectrum<T, N> {
+  type Item = &'a Eigenvalue<T>;
+  type IntoIter = std::slice::Iter<'a, Eigenvalue<T>>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -0,0 +1,205 @@
+use crate::norms::norm_max;
+use crate::utils::*;
+
+use glam::{DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+
+/// Computes the singular value decomposition of a dense 2x2 matrix
+// A is split into a scaled rotation [e -h; h e] and a scaled reflection [f g; g -f], which combine into
+// A = Rot(φ) diag(q + r, q - r) Rot(ψ) for the magnitudes q and r and the angles of the two parts
+pub(crate) fn svd2(A: DMat2) -> (DMat2, [f64; 2], DMat2) {
+  let [[a, c], [b, d]] = A.to_cols_array_2d();
+  let (e, f) = (0.5 * (a + d), 0.5 * (a - d));
+  let (g, h) = (0.5 * (c + b), 0.5 * (c - b));
+
+  let [s1, s2] = singular_values2_signed(A, e.hypot(h), f.hypot(g));
+  let (a1, a2) = (g.atan2(f), h.atan2(e));
+
+  let U = DMat2::from_angle(0.5 * (a2 + a1));
+  let mut Vt = DMat2::from_angle(0.5 * (a2 - a1));
+
+  // A negative second singular value is absorbed into the second row of Vᵀ
+  if s2 < 0.0 {
+    Vt.x_axis.y = -Vt.x_axis.y;
+    Vt.y_axis.y = -Vt.y_axis.y;
+  }
+
+  (U, [s1, s2.abs()], Vt)
+}
+
+/// Computes the singular values of a dense 2x2 matrix
+pub(crate) fn singular_values2(A: DMat2) -> [f64; 2] {
+  let [[a, c], [b, d]] = A.to_cols_array_2d();
+  let q = (0.5 * (a + d)).hypot(0.5 * (c - b));
+  let r = (0.5 * (a - d)).hypot(0.5 * (c + b));
+  singular_values2_signed(A, q, r).map(f64::abs)
+}
+
+// The smaller singular value q - r is computed as det(A) / (q + r) to avoid cancellation
+fn singular_values2_signed(A: DMat2, q: f64, r: f64) -> [f64; 2] {
+  let s1 = q + r;
+  let s2 = if s1 == 0.0 { 0.0 } else { A.determinant() / s1 };
+  [s1, s2]
+}
+
+/// Computes the singular value decomposition of a dense 3x3 matrix
+pub(crate) fn svd3(A: DMat3) -> (DMat3, [f64; 3], DMat3) {
+  let (U, sigma, V) = svd_jacobi(A.to_cols_array_2d());
+  let U = DMat3::from_cols_array_2d(&U);
+  let V = DMat3::from_cols_array_2d(&V);
+  (U, sigma, V.transpose())
+}
+
+/// Computes the singular values of a dense 3x3 matrix
+pub(crate) fn singular_values3(A: DMat3) -> [f64; 3] {
+  singular_values_jacobi(A.to_cols_array_2d())
+}
+
+/// Computes the singular value decomposition of a dense 4x4 matrix
+pub(crate) fn svd4(A: DMat4) -> (DMat4, [f64; 4], DMat4) {
+  let (U, sigma, V) = svd_jacobi(A.to_cols_array_2d());
+  let U = DMat4::from_cols_array_2d(&U);
+  let V = DMat4::from_cols_array_2d(&V);
+  (U, sigma, V.transpose())
+}
+
+/// Computes the singular values of a dense 4x4 matrix
+pub(crate) fn singular_values4(A: DMat4) -> [f64; 4] {
+  singular_values_jacobi(A.to_cols_array_2d())
+}
+
+/// Computes the singular value decomposition A = U diag(σ) Vᵀ with the one-sided Jacobi method
+// The matrices are given as arrays of columns. Once the columns of A V are mutually orthogonal,
+// their lengths are the singular values and their directions are the columns of U
+// The matrix is first normalized, see normalization
+pub(crate) fn svd_jacobi<const N: usize>(
+  A: [[f64; N]; N],
+) -> ([[f64; N]; N], [f64; N], [[f64; N]; N]) {
+  let scale = normalization(norm_max(&A));
+  let mut A = A.map(|x| x.map(|x| x / scale));
+  let mut V = [[0.0; N]; N];
+  (0..N).for_each(|i| V[i][i] = 1.0);
+  orthogonalize_cols(&mut A, Some(&mut V));
+
+  // Sort the singular values in descending order
+  let norms = A.map(|x| dot(&x, &x).sqrt());
+  let mut order: [usize; N] = std::array::from_fn(|k| k);
+  order.sort_by(|&i, &j| norms[j].total_cmp(&norms[i]));
+
+  let sigma = order.map(|k| norms[k]);
+  let V = order.map(|k| V[k]);
+  let mut U = order.map(|k| A[k]);
+
+  for k in 0..N {
+    if sigma[k] > 0.0 {
+      U[k] = U[k].map(|x| x / sigma[k]);
+    } else {
+      // The zero singular values come last, so the columns before k are already orthonormal
+      U[k] = orthonormal_complement(&U[..k]);
+    }
+  }
+
+  (U, sigma.map(|s| s * scale), V)
+}
+
+/// Computes the singular values of a square matrix with the one-sided Jacobi method, in descending order
+pub(crate) fn singular_values_jacobi<T: Real, const N: usize>(A: [[T; N]; N]) -> [T; N] {
+  let scale = normalization(norm_max(&A));
+  let mut A = A.map(|x| x.map(|x| x / scale));
+  orthogonalize_cols(&mut A, None);
+  let mut sigma = A.map(|x| dot(&x, &x).sqrt() * scale);
+  sigma.sort_by(|a, b| b.total_cmp(a));
+  sigma
+}
+
+/// Rotates pairs of columns of A until they are mutually orthogonal, accumulating the rotations into V
+fn orthogonalize_cols<T: Real, const N: usize>(
+  A: &mut [[T; N]; N],
+  mut V: Option<&mut [[T; N]; N]>,
+) {
+  // Jacobi converges quadratically, so this limit is never reached in practice
+  for _ in 0..64 {
+    let mut rotated = false;
+
+    for p in 0..N {
+      for q in p + 1..N {
+        let alpha = dot(&A[p], &A[p]);
+        let beta = dot(&A[q], &A[q]);
+        let gamma = dot(&A[p], &A[q]);
+        if gamma.abs() <= T::EPSILON * alpha.sqrt() * beta.sqrt() || gamma == T::ZERO {
+          continue;
+        }
+        rotated = true;
+
+        // The rotation that makes columns p and q orthogonal
+        let zeta = (beta - alpha) / (T::from_f64(2.0) * gamma);
+        let t = zeta.signum() / (zeta.abs() + zeta.hypot(T::ONE));
+        let c = (t * t + T::ONE).sqrt().recip();
+        let s = t * c;
+
+        rotate_cols(A, p, q, c, s);
+        if let Some(V) = V.as_deref_mut() {
+          rotate_cols(V, p, q, c, s);
+        }
+      }
+    }
+
+    if !rotated {
+      break;
+    }
+  }
+}
+
+/// Finds a unit vector orthogonal to the given orthonormal vectors, by Gram-Schmidt on the standard basis
+fn orthonormal_complement<const N: usize>(basis: &[[f64; N]]) -> [f64; N] {
+  let project = |mut x: [f64; N]| {
+    for u in basis {
+      let d = dot(&x, u);
+      x.iter_mut().zip(u).for_each(|(x, u)| *x -= d * u);
+    }
+    x
+  };
+
+  // The standard basis vector furthest from the span of the basis is the best conditioned choice
+  let x = (0..N)
+    .map(|i| project(std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 })))
+    .max_by(|x, y| dot(x, x).total_cmp(&dot(y, y)))
+    .unwrap();
+
+  // Orthogonalize twice for accuracy
+  let x = project(x);
+  let norm = dot(&x, &x).sqrt();
+  x.map(|x| x / norm)
+}
+
+/// Computes the pseudo-inverse of a dense 2x2 matrix and its numerical rank
+pub(crate) fn pinv2(A: DMat2, rcond: f64) -> (DMat2, usize) {
+  let (U, sigma, Vt) = svd2(A);
+  let (sigma_inv, rank) = invert_singular_values(sigma, rcond);
+  let S = DMat2::from_diagonal(DVec2::from(sigma_inv));
+  (Vt.transpose() * S * U.transpose(), rank)
+}
+
+/// Computes the pseudo-inverse of a dense 3x3 matrix and its numerical rank
+pub(crate) fn pinv3(A: DMat3, rcond: f64) -> (DMat3, usize) {
+  let (U, sigma, Vt) = svd3(A);
+  let (sigma_inv, rank) = invert_singular_values(sigma, rcond);
+  let S = DMat3::from_diagonal(DVec3::from(sigma_inv));
+  (Vt.transpose() * S * U.transpose(), rank)
+}
+
+/// Computes the pseudo-inverse of a dense 4x4 matrix and its numerical rank
+pub(crate) fn pinv4(A: DMat4, rcond: f64) -> (DMat4, usize) {
+  let (U, sigma, Vt) = svd4(A);
+  let (sigma_inv, rank) = invert_singular_values(sigma, rcond);
+  let S = DMat4::from_diagonal(DVec4::from(sigma_inv));
+  (Vt.transpose() * S * U.transpose(), rank)
+}
+
+/// Inverts the singular values above rcond σ_max and zeroes the rest, counting the ones kept
+// The singular values are in descending order, so the kept ones are a prefix
+fn invert_singular_values<const N: usize>(sigma: [f64; N], rcond: f64) -> ([f64; N], usize) {
+  let tol = rcond * sigma[0];
+  let rank = sigma.iter().take_while(|&&s| s > tol).count();
+  let sigma_inv = std::array::from_fn(|k| if k < rank { sigma[k].recip() } else { 0.0 });
+  (sigma_inv, rank)
+}
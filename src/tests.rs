@@ -1,53 +1,79 @@
+mod cholesky;
+mod complex;
 mod eigvals2;
 mod eigvals3;
 mod eigvals4;
+mod errors;
+mod f32;
+mod functions;
+mod hessenberg;
+mod least_squares;
+mod lu;
+mod norms;
+mod options;
+mod order;
+mod polar;
+mod predicates;
+mod qr;
+mod schur;
+mod spectrum;
+mod svd;
 
 use crate::utils::*;
+use crate::Complex64;
 
 use approx::assert_abs_diff_eq;
 use fastrand::Rng;
-use glam::DVec2;
 use paste::paste;
 
-// TODO: The symmetric cases should not have a lower epsilon. At the moment, this is due to eigvals3_symmetric algorithm
 pub(crate) const EPS: f64 = 1e-12;
-pub(crate) const SYMMETRIC_EPS: f64 = 1e-8;
 
-pub(crate) fn sorted<const N: usize>(mut x: [f64; N]) -> [f64; N] {
-  x.sort_by(|a, b| a.total_cmp(b));
-  x
-}
-
-pub(crate) fn csorted<const N: usize>(mut x: [DVec2; N]) -> [DVec2; N] {
-  x.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
-  x
-}
-
-pub(crate) fn assert_dvec2_eq(a: DVec2, b: DVec2, eps: f64) {
+pub(crate) fn assert_complex_eq(a: Complex64, b: Complex64, eps: f64) {
   assert!(a.abs_diff_eq(b, eps));
 }
 
 /// The tests here are from https://math.stackexchange.com/a/894641
-pub(crate) fn assert_valid(eigvals: &[DVec2], trace: f64, trace_sq: f64, det: f64, eps: f64) {
+pub(crate) fn assert_valid(eigvals: &[Complex64], trace: f64, trace_sq: f64, det: f64, eps: f64) {
   // The sum of the eigenvalues should be equal to the trace
-  let sum: DVec2 = eigvals.iter().copied().sum();
-  assert_dvec2_eq(sum, complex(trace), eps);
+  let sum: Complex64 = eigvals.iter().sum();
+  assert_complex_eq(sum, complex(trace), eps);
 
   // The sum of the eigenvalues squared should be equal to the trace of the matrix times itself
   let sum: f64 = eigvals
     .iter()
-    .map(|&lambda| lambda.x.powi(2) - lambda.y.powi(2))
+    .map(|&lambda| lambda.re.powi(2) - lambda.im.powi(2))
     .sum();
   assert_abs_diff_eq!(sum, trace_sq, epsilon = eps);
 
   // The product of the eigenvalues should be equal to the determinant
-  let sign: f64 = eigvals.iter().map(|&lambda| lambda.x.signum()).product();
-  let prod: f64 = eigvals.iter().map(|&lambda| lambda.length()).product();
+  let sign: f64 = eigvals.iter().map(|&lambda| lambda.re.signum()).product();
+  let prod: f64 = eigvals.iter().map(|&lambda| lambda.abs()).product();
   assert_abs_diff_eq!(sign * prod, det, epsilon = eps);
 }
 
-const SEED: u64 = 1234;
-const N_TESTS: u64 = 100_000;
+/// Asserts that each column of re + i * im is a unit eigenvector of A for the matching eigenvalue
+// The matrices are given as arrays of columns
+pub(crate) fn assert_eigvecs<const N: usize>(
+  A: [[f64; N]; N],
+  eigvals: &[Complex64; N],
+  re: [[f64; N]; N],
+  im: [[f64; N]; N],
+  eps: f64,
+) {
+  for k in 0..N {
+    let v: Vec<_> = (0..N).map(|i| Complex64::new(re[k][i], im[k][i])).collect();
+    let norm: f64 = v.iter().map(|x| x.norm_sqr()).sum();
+    assert_abs_diff_eq!(norm, 1.0, epsilon = eps);
+
+    for i in 0..N {
+      let Av: Complex64 = (0..N).map(|j| A[j][i] * v[j]).sum();
+      assert_complex_eq(Av, eigvals[k] * v[i], eps);
+    }
+  }
+}
+
+pub(crate) const SEED: u64 = 1234;
+pub(crate) const N_TESTS: u64 = 100_000;
 
 macro_rules! test_dmat_rand {
   ($num:expr) => {
@@ -74,6 +100,50 @@ macro_rules! test_dmat_rand {
         }
       }
 
+      #[test]
+      fn [< test_eigvals $num _rand_scaled>]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        // The eigenvalues should scale with the matrix, even where its squared entries overflow or underflow
+        for _ in 0..N_TESTS / 10 {
+          let A = [< rand_dmat $num >](&mut rng);
+          for scale in [1e150, 1e200, 1e-150, 1e-200] {
+            let eigvals = (A * scale).eigvals().map(|lambda| lambda / scale);
+            assert_valid(
+              &eigvals,
+              A.trace(),
+              (A * A).trace(),
+              A.determinant(),
+              EPS,
+            );
+          }
+        }
+      }
+
+      #[test]
+      fn [< test_eig $num _rand>]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let A = [< rand_dmat $num >](&mut rng);
+          let (eigvals, re, im) = A.eig();
+          assert_valid(
+            &eigvals,
+            A.trace(),
+            (A * A).trace(),
+            A.determinant(),
+            EPS,
+          );
+          assert_eigvecs(
+            A.to_cols_array_2d(),
+            &eigvals,
+            re.to_cols_array_2d(),
+            im.to_cols_array_2d(),
+            EPS,
+          );
+        }
+      }
+
       #[test]
       fn [< test_eigvals $num _symmetric_rand>]() {
         let mut rng = Rng::with_seed(SEED);
@@ -87,11 +157,25 @@ macro_rules! test_dmat_rand {
             A.trace(),
             (A * A).trace(),
             A.determinant(),
-            SYMMETRIC_EPS,
+            EPS,
           );
         }
       }
 
+      #[test]
+      fn [< test_eig $num _symmetric_rand>]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let A = [< rand_dmat $num >](&mut rng);
+          let A = A + A.transpose();
+          let (eigvals, V) = A.eig_symmetric();
+          let D = [< DMat $num >]::from_diagonal(glam::[< DVec $num >]::from_array(eigvals));
+          assert!((V * D * V.transpose()).abs_diff_eq(A, EPS));
+          assert!((V.transpose() * V).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+        }
+      }
+
     }
   };
 }
@@ -0,0 +1,168 @@
+use crate::tests::*;
+use crate::*;
+
+use fastrand::Rng;
+use glam::{dvec2, dvec3, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+use paste::paste;
+
+/// Asserts that the Cholesky decomposition of the positive definite matrix A is valid and solves A x = b
+macro_rules! assert_cholesky {
+  ($num:expr, $A:expr, $b:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let chol = A.cholesky().unwrap();
+      assert!((chol.L * chol.L.transpose()).abs_diff_eq(A, EPS));
+
+      let L = chol.L.to_cols_array_2d();
+      for j in 0..$num {
+        assert!(L[j][j] > 0.0);
+        for i in 0..j {
+          assert_eq!(L[j][i], 0.0);
+        }
+      }
+
+      let b: [< DVec $num >] = $b;
+      let x = chol.solve(b);
+      assert!((A * x).abs_diff_eq(b, 1e-9 * x.length().max(1.0)));
+
+      let X = chol.solve_mat([< DMat $num >]::IDENTITY).to_cols_array_2d();
+      let expected = [< DMat $num >]::IDENTITY.to_cols_array_2d().map(|b| chol.solve(b.into()).to_array());
+      assert_eq!(X, expected);
+    }}
+  };
+}
+
+/// Asserts that the LDLᵀ decomposition of the symmetric matrix A is valid and solves A x = b
+macro_rules! assert_ldlt {
+  ($num:expr, $A:expr, $b:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let ldlt = A.ldlt();
+      let P = ldlt.permutation();
+      assert!((ldlt.L * ldlt.D * ldlt.L.transpose()).abs_diff_eq(P * A * P.transpose(), EPS));
+
+      // L is unit lower triangular and D is symmetric block diagonal
+      let L = ldlt.L.to_cols_array_2d();
+      let D = ldlt.D.to_cols_array_2d();
+      for j in 0..$num {
+        assert_eq!(L[j][j], 1.0);
+        for i in 0..j {
+          assert_eq!(L[j][i], 0.0);
+        }
+        for i in j + 1..$num {
+          assert_eq!(D[j][i], D[i][j]);
+          if i > j + 1 {
+            assert_eq!(D[j][i], 0.0);
+          }
+        }
+      }
+
+      if !ldlt.singular {
+        let b: [< DVec $num >] = $b;
+        let x = ldlt.solve(b).unwrap();
+        assert!((A * x).abs_diff_eq(b, 1e-9 * x.length().max(1.0)));
+
+        let X = ldlt.solve_mat([< DMat $num >]::IDENTITY).unwrap().to_cols_array_2d();
+        let expected = [< DMat $num >]::IDENTITY.to_cols_array_2d().map(|b| ldlt.solve(b.into()).unwrap().to_array());
+        assert_eq!(X, expected);
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_cholesky() {
+  let A = DMat3::from_cols_array(&[4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]);
+  assert_cholesky!(3, A, dvec3(1.0, 2.0, 3.0));
+  let L = DMat3::from_cols_array(&[2.0, 6.0, -8.0, 0.0, 1.0, 5.0, 0.0, 0.0, 3.0]);
+  assert!(A.cholesky().unwrap().L.abs_diff_eq(L, EPS));
+  assert_ldlt!(3, A, dvec3(1.0, 2.0, 3.0));
+
+  // Only the lower triangle is read
+  let mut B = A;
+  B.z_axis.x = f64::NAN;
+  assert_eq!(B.cholesky(), A.cholesky());
+  assert_eq!(B.ldlt(), A.ldlt());
+
+  // Semidefinite, indefinite and non-finite matrices have no Cholesky decomposition
+  assert!(DMat2::ZERO.cholesky().is_none());
+  assert!(DMat2::from_cols_array(&[1.0, 1.0, 1.0, 1.0])
+    .cholesky()
+    .is_none());
+  assert!(DMat2::from_diagonal(dvec2(1.0, -1.0)).cholesky().is_none());
+  assert!(DMat2::from_diagonal(dvec2(1.0, f64::NAN))
+    .cholesky()
+    .is_none());
+}
+
+#[test]
+fn test_ldlt() {
+  // Indefinite matrices with a zero diagonal need 2x2 pivots
+  let A = DMat2::from_cols_array(&[0.0, 1.0, 1.0, 0.0]);
+  assert_ldlt!(2, A, dvec2(1.0, 2.0));
+  assert!(!A.ldlt().singular);
+  assert_eq!(A.ldlt().solve(dvec2(1.0, 2.0)), Some(dvec2(2.0, 1.0)));
+
+  let A = DMat4::from_cols_array(&[
+    0.0, 1.0, 2.0, 3.0, 1.0, 0.0, 4.0, 5.0, 2.0, 4.0, 0.0, 6.0, 3.0, 5.0, 6.0, 0.0,
+  ]);
+  assert_ldlt!(4, A, DVec4::ONE);
+  assert!(!A.ldlt().singular);
+
+  // Semidefinite matrices are factored but flagged as singular
+  let v = dvec3(1.0, -2.0, 0.5);
+  for A in [DMat3::ZERO, DMat3::from_cols(v, -2.0 * v, 0.5 * v)] {
+    assert_ldlt!(3, A, DVec3::ONE);
+    assert!(A.ldlt().singular);
+    assert_eq!(A.ldlt().solve(DVec3::ONE), None);
+  }
+  let A = DMat2::from_cols_array(&[1.0, 1.0, 1.0, 1.0]);
+  assert_ldlt!(2, A, DVec2::ONE);
+  assert!(A.ldlt().singular);
+
+  // A 2x2 pivot whose entries are tiny relative to A is nearly singular, with eigenvalues ±1e-17
+  let A = DMat3::from_cols(
+    dvec3(1e-20, 1e-17, 0.0),
+    dvec3(1e-17, 0.0, 0.0),
+    dvec3(0.0, 0.0, 1.0),
+  );
+  let ldlt = A.ldlt();
+  assert_ne!(ldlt.D.x_axis.y, 0.0);
+  assert!(ldlt.singular);
+  assert_eq!(ldlt.solve(DVec3::ONE), None);
+
+  // Non-finite input is flagged as singular rather than reaching a 2x2 pivot at the last row
+  assert!((DMat2::IDENTITY * f64::NAN).ldlt().singular);
+  assert!((DMat3::IDENTITY * f64::NAN).ldlt().singular);
+  assert!((DMat4::IDENTITY * f64::NAN).ldlt().singular);
+  assert!((DMat3::IDENTITY * f64::INFINITY).ldlt().singular);
+  assert_eq!((DMat4::IDENTITY * f64::NAN).ldlt().solve(DVec4::ONE), None);
+}
+
+macro_rules! test_cholesky_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_cholesky $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let b: Vec<_> = (0..$num).map(|_| rng.f64() - 0.5).collect();
+          let b = [< DVec $num >]::from_slice(&b);
+          let M = [< DMat $num >]::from_cols_slice(&v);
+
+          // M Mᵀ is positive definite and M + Mᵀ is generally indefinite
+          let A = M * M.transpose() + 1e-3 * [< DMat $num >]::IDENTITY;
+          assert_cholesky!($num, A, b);
+          assert_ldlt!($num, A, b);
+          assert_ldlt!($num, M + M.transpose(), b);
+        }
+      }
+    }
+  };
+}
+
+test_cholesky_rand!(2);
+test_cholesky_rand!(3);
+test_cholesky_rand!(4);
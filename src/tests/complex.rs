@@ -0,0 +1,91 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+#[test]
+fn test_complex() {
+  let a = Complex64::new(1.0, 2.0);
+  let b = Complex64::new(-3.0, 0.5);
+
+  assert_eq!(a + b, Complex64::new(-2.0, 2.5));
+  assert_eq!(a - b, Complex64::new(4.0, 1.5));
+  assert_eq!(a * b, Complex64::new(-4.0, -5.5));
+  assert_complex_eq((a * b) / b, a, EPS);
+  assert_eq!(-a, Complex64::new(-1.0, -2.0));
+  assert_eq!(a.conj(), Complex64::new(1.0, -2.0));
+  assert_eq!(Complex64::I * Complex64::I, -Complex64::ONE);
+
+  // Mixed real and complex arithmetic
+  assert_eq!(2.0 * a, Complex64::new(2.0, 4.0));
+  assert_eq!(a / 2.0, Complex64::new(0.5, 1.0));
+  assert_eq!(1.0 - a, Complex64::new(0.0, -2.0));
+  assert_complex_eq(1.0 / Complex64::I, -Complex64::I, EPS);
+  let mut c = a;
+  c += b;
+  c *= 2.0;
+  assert_eq!(c, Complex64::new(-4.0, 5.0));
+  assert_eq!([a, b].iter().sum::<Complex64>(), a + b);
+  assert_eq!([a, b].into_iter().product::<Complex64>(), a * b);
+
+  assert_eq!(Complex64::new(3.0, 4.0).abs(), 5.0);
+  assert_eq!(Complex64::new(3.0, 4.0).norm_sqr(), 25.0);
+  assert_eq!(Complex64::I.arg(), FRAC_PI_2);
+  assert_eq!(Complex64::from(-1.0).arg(), PI);
+
+  // Division does not overflow for large components
+  let big = Complex64::new(1e300, 1e300);
+  assert_complex_eq(big / big, Complex64::ONE, EPS);
+
+  // The principal square root, with the branch cut along the negative real axis
+  assert_eq!(Complex64::from(-4.0).sqrt(), Complex64::new(0.0, 2.0));
+  assert_eq!(Complex64::new(-4.0, -0.0).sqrt(), Complex64::new(0.0, -2.0));
+  assert_eq!(Complex64::new(3.0, 4.0).sqrt(), Complex64::new(2.0, 1.0));
+  assert_eq!(Complex64::ZERO.sqrt(), Complex64::ZERO);
+
+  // exp and ln
+  assert_complex_eq((Complex64::I * PI).exp(), -Complex64::ONE, EPS);
+  assert_complex_eq(Complex64::from(-1.0).ln(), Complex64::new(0.0, PI), EPS);
+  assert_complex_eq(
+    Complex64::from_polar(2.0, 0.5),
+    Complex64::new(2.0 * 0.5f64.cos(), 2.0 * 0.5f64.sin()),
+    EPS,
+  );
+
+  assert_eq!(a.to_string(), "1+2i");
+  assert_eq!(a.conj().to_string(), "1-2i");
+  assert_eq!(
+    Complex32::new(1.5, -0.25).as_complex64(),
+    Complex64::new(1.5, -0.25)
+  );
+  assert!(Complex64::new(f64::NAN, 0.0).is_nan());
+  assert!(!Complex64::new(f64::INFINITY, 0.0).is_finite());
+}
+
+#[test]
+fn test_complex_rand() {
+  let mut rng = Rng::with_seed(SEED);
+
+  for _ in 0..N_TESTS {
+    let z = Complex64::new(rng.f64() - 0.5, rng.f64() - 0.5) * 10.0;
+    let w = Complex64::new(rng.f64() - 0.5, rng.f64() - 0.5) * 10.0;
+
+    let r = z.sqrt();
+    assert!(r.re >= 0.0);
+    assert_complex_eq(r * r, z, EPS);
+    assert_complex_eq(z.ln().exp(), z, EPS);
+    assert_complex_eq(z * z.recip(), Complex64::ONE, EPS);
+    assert_complex_eq(z / w * w, z, 1e-10);
+    assert_abs_diff_eq!((z * w).abs(), z.abs() * w.abs(), epsilon = EPS);
+  }
+}
+
+#[test]
+#[cfg(feature = "num-complex")]
+fn test_num_complex() {
+  let z = num_complex::Complex64::new(1.0, -2.0);
+  assert_eq!(Complex64::from(z), Complex64::new(1.0, -2.0));
+  assert_eq!(num_complex::Complex64::from(Complex64::from(z)), z);
+}
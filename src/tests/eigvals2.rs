@@ -3,37 +3,85 @@ use crate::utils::*;
 use crate::*;
 
 use approx::assert_abs_diff_eq;
-use glam::{dvec2, DMat2};
+use glam::{DMat2, DVec2};
 
 #[test]
 fn test_eigvals2() {
-  let [a, b] = csorted(DMat2::from_cols_array(&[1.0, -1.0, -1.0, 1.0]).eigvals());
-  assert_dvec2_eq(a, dvec2(0.0, 0.0), EPS);
-  assert_dvec2_eq(b, dvec2(2.0, 0.0), EPS);
+  let [a, b] = DMat2::from_cols_array(&[1.0, -1.0, -1.0, 1.0]).eigvals();
+  assert_complex_eq(a, Complex64::new(0.0, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(2.0, 0.0), EPS);
 
-  let [a, b] = csorted(DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]).eigvals());
-  assert_dvec2_eq(a, dvec2(-0.3722813232690143, 0.0), EPS);
-  assert_dvec2_eq(b, dvec2(5.372281323269014, 0.0), EPS);
+  let [a, b] = DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]).eigvals();
+  assert_complex_eq(a, Complex64::new(-0.3722813232690143, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(5.372281323269014, 0.0), EPS);
 
-  let [a, b] = csorted(DMat2::from_cols_array(&[1.0, -1.0, 1.0, 1.0]).eigvals());
-  assert_dvec2_eq(a, dvec2(1.0, -1.0), EPS);
-  assert_dvec2_eq(b, dvec2(1.0, 1.0), EPS);
+  let [a, b] = DMat2::from_cols_array(&[1.0, -1.0, 1.0, 1.0]).eigvals();
+  assert_complex_eq(a, Complex64::new(1.0, -1.0), EPS);
+  assert_complex_eq(b, Complex64::new(1.0, 1.0), EPS);
 }
 
 #[test]
 fn test_eigvals2_symmetric() {
   // These two examples are the symmetric test cases from test_eigvals2
-  let [a, b] = sorted(eigvals2_symmetric(DMat2::from_cols_array(&[
-    1.0, -1.0, -1.0, 1.0,
-  ])));
+  let [a, b] = eigvals2_symmetric(DMat2::from_cols_array(&[1.0, -1.0, -1.0, 1.0]));
   assert_abs_diff_eq!(a, 0.0, epsilon = EPS);
   assert_abs_diff_eq!(b, 2.0, epsilon = EPS);
 
-  let [a, b] = sorted(eigvals2_symmetric(DMat2::from_cols_array(&[
-    1.0, 2.0, 3.0, 4.0,
-  ])));
+  let [a, b] = eigvals2_symmetric(DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
   assert_abs_diff_eq!(a, -0.3722813232690143, epsilon = EPS);
   assert_abs_diff_eq!(b, 5.372281323269014, epsilon = EPS);
+
+  // Close eigenvalues, which are lost to cancellation in the discriminant t²/4 - d
+  let delta = 1e-9;
+  let [a, b] = eigvals2_symmetric(DMat2::from_cols_array(&[
+    1.0 / 3.0,
+    delta,
+    delta,
+    1.0 / 3.0,
+  ]));
+  assert_abs_diff_eq!(a, 1.0 / 3.0 - delta, epsilon = 1e-15);
+  assert_abs_diff_eq!(b, 1.0 / 3.0 + delta, epsilon = 1e-15);
+
+  // A tiny eigenvalue next to a large one, which is lost to cancellation in center - gap
+  let A = DMat2::from_diagonal(DVec2::new(1.0, 1e-20));
+  assert_eq!(eigvals2_symmetric(A), [1e-20, 1.0]);
+  assert_eq!(A.eigvals(), [complex(1e-20), complex(1.0)]);
+  assert_eq!(eigvals2_symmetric(-A), [-1.0, -1e-20]);
+}
+
+#[test]
+fn test_eig2() {
+  for A in [
+    DMat2::from_cols_array(&[1.0, -1.0, 1.0, 1.0]),
+    DMat2::from_cols_array(&[2.0, 0.0, 0.0, 2.0]),
+    DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]),
+    DMat2::from_cols_array(&[0.0, 0.0, 1.0, 0.0]),
+  ] {
+    let (eigvals, re, im) = A.eig();
+    assert_eigvecs(
+      A.to_cols_array_2d(),
+      &eigvals,
+      re.to_cols_array_2d(),
+      im.to_cols_array_2d(),
+      EPS,
+    );
+  }
+}
+
+#[test]
+fn test_eig2_symmetric() {
+  for A in [
+    DMat2::from_cols_array(&[1.0, -1.0, -1.0, 1.0]),
+    DMat2::from_cols_array(&[1.0, 2.0, 2.0, 4.0]),
+    DMat2::from_cols_array(&[3.0, 0.0, 0.0, -1.0]),
+    DMat2::IDENTITY,
+    DMat2::from_cols_array(&[1.0 / 3.0, 1e-9, 1e-9, 1.0 / 3.0]),
+  ] {
+    let (eigvals, V) = A.eig_symmetric();
+    let D = DMat2::from_diagonal(DVec2::from_array(eigvals));
+    assert!((V * D * V.transpose()).abs_diff_eq(A, 1e-15));
+    assert!((V.transpose() * V).abs_diff_eq(DMat2::IDENTITY, EPS));
+  }
 }
 
 test_dmat_rand!(2);
@@ -3,93 +3,229 @@ use crate::utils::*;
 use crate::*;
 
 use approx::assert_abs_diff_eq;
-use glam::{dvec2, DMat4};
+use glam::{dvec4, DMat4, DVec4};
 
 #[test]
 fn test_eigvals4() {
-  let [a, b, c, d] = csorted(
-    DMat4::from_cols_array(&[
-      1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
-    ])
-    .eigvals(),
-  );
-  assert_dvec2_eq(a, dvec2(-2.2093727122985456, 0.0), EPS);
-  assert_dvec2_eq(b, dvec2(0.0, 0.0), EPS);
-  assert_dvec2_eq(c, dvec2(0.0, 0.0), EPS);
-  assert_dvec2_eq(d, dvec2(36.20937271229853, 0.0), EPS);
+  let [a, b, c, d] = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+  ])
+  .eigvals();
+  assert_complex_eq(a, Complex64::new(-2.2093727122985456, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(0.0, 0.0), EPS);
+  assert_complex_eq(c, Complex64::new(0.0, 0.0), EPS);
+  assert_complex_eq(d, Complex64::new(36.20937271229853, 0.0), EPS);
 
-  let [a, b, c, d] = csorted(
-    DMat4::from_cols_array(&[
-      1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
-    ])
-    .eigvals(),
+  let [a, b, c, d] = DMat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ])
+  .eigvals();
+  assert_complex_eq(a, Complex64::new(-0.4476229868548985, 0.0), EPS);
+  assert_complex_eq(
+    b,
+    Complex64::new(0.6976613211204787, -0.4951597570817051),
+    EPS,
+  );
+  assert_complex_eq(
+    c,
+    Complex64::new(0.6976613211204787, 0.4951597570817051),
+    EPS,
   );
-  assert_dvec2_eq(a, dvec2(-0.4476229868548985, 0.0), EPS);
-  assert_dvec2_eq(b, dvec2(0.6976613211204787, -0.4951597570817051), EPS);
-  assert_dvec2_eq(c, dvec2(0.6976613211204787, 0.4951597570817051), EPS);
-  assert_dvec2_eq(d, dvec2(3.0523003446139394, 0.0), EPS);
+  assert_complex_eq(d, Complex64::new(3.0523003446139394, 0.0), EPS);
 
   // Hilbert matrix
-  let [a, b, c, d] = csorted(
-    DMat4::from_cols_array(
-      &[
-        1f64, 2f64, 3f64, 4f64, 2f64, 3f64, 4f64, 5f64, 3f64, 4f64, 5f64, 6f64, 4f64, 5f64, 6f64,
-        7f64,
-      ]
-      .map(|x| x.recip()),
-    )
-    .eigvals(),
-  );
-  assert_dvec2_eq(a, dvec2(9.670230402261436e-5, 0.0), EPS);
-  assert_dvec2_eq(b, dvec2(0.006738273605760762, 0.0), EPS);
-  assert_dvec2_eq(c, dvec2(0.16914122022145014, 0.0), EPS);
-  assert_dvec2_eq(d, dvec2(1.5002142800592426, 0.0), EPS);
+  let [a, b, c, d] = DMat4::from_cols_array(
+    &[
+      1f64, 2f64, 3f64, 4f64, 2f64, 3f64, 4f64, 5f64, 3f64, 4f64, 5f64, 6f64, 4f64, 5f64, 6f64,
+      7f64,
+    ]
+    .map(|x| x.recip()),
+  )
+  .eigvals();
+  assert_complex_eq(a, Complex64::new(9.670230402261436e-5, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(0.006738273605760762, 0.0), EPS);
+  assert_complex_eq(c, Complex64::new(0.16914122022145014, 0.0), EPS);
+  assert_complex_eq(d, Complex64::new(1.5002142800592426, 0.0), EPS);
 
   // A matrix with a low condition number, which is a bad case for the base QR algorithm
   let delta = 1e-5;
-  let [a, b, c, d] = csorted(
-    DMat4::from_cols_array(&[
-      1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -delta, 0.0, 0.0, delta, 1.0,
-    ])
-    .eigvals(),
-  );
-  assert_dvec2_eq(a, dvec2(1.0, -delta), EPS);
-  assert_dvec2_eq(b, dvec2(1.0, 0.0), EPS);
-  assert_dvec2_eq(c, dvec2(1.0, 0.0), EPS);
-  assert_dvec2_eq(d, dvec2(1.0, delta), EPS);
+  let [a, b, c, d] = DMat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, -delta, 0.0, 0.0, delta, 1.0,
+  ])
+  .eigvals();
+  // Among equal real parts, the real eigenvalues come before the conjugate pair
+  assert_complex_eq(a, Complex64::new(1.0, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(1.0, 0.0), EPS);
+  assert_complex_eq(c, Complex64::new(1.0, -delta), EPS);
+  assert_complex_eq(d, Complex64::new(1.0, delta), EPS);
+
+  // The shift must come from the trailing 2x2 block, otherwise the iteration stalls on this matrix
+  let A = DMat4::from_cols_array(&[
+    -0.35752052621064445,
+    -0.3478969091896588,
+    0.1297629894693674,
+    0.1306064289894242,
+    -0.09386706157954622,
+    -0.2016114290237256,
+    -0.05668761697350104,
+    -0.43173948797504336,
+    0.006067188207204115,
+    -0.3933518282882097,
+    -0.24858117284473175,
+    0.43046701832667605,
+    -0.4614407480554261,
+    0.2605372440030641,
+    0.09213187533027455,
+    0.44666460339847375,
+  ]);
+  let eigvals = A.eigvals();
+  assert_valid(&eigvals, A.trace(), (A * A).trace(), A.determinant(), EPS);
 }
 
 #[test]
 fn test_eigvals4_symmetric() {
   // These two test cases are the symmetric test cases from test_eigvals4
   // Hilbert matrix
-  let [a, b, c, d] = sorted(
-    DMat4::from_cols_array(
-      &[
-        1f64, 2f64, 3f64, 4f64, 2f64, 3f64, 4f64, 5f64, 3f64, 4f64, 5f64, 6f64, 4f64, 5f64, 6f64,
-        7f64,
-      ]
-      .map(|x| x.recip()),
-    )
-    .eigvals_symmetric(),
-  );
-  assert_abs_diff_eq!(a, 9.670230402261436e-5, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(b, 0.006738273605760762, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(c, 0.16914122022145014, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(d, 1.5002142800592426, epsilon = SYMMETRIC_EPS);
+  let [a, b, c, d] = DMat4::from_cols_array(
+    &[
+      1f64, 2f64, 3f64, 4f64, 2f64, 3f64, 4f64, 5f64, 3f64, 4f64, 5f64, 6f64, 4f64, 5f64, 6f64,
+      7f64,
+    ]
+    .map(|x| x.recip()),
+  )
+  .eigvals_symmetric();
+  assert_abs_diff_eq!(a, 9.670230402261436e-5, epsilon = EPS);
+  assert_abs_diff_eq!(b, 0.006738273605760762, epsilon = EPS);
+  assert_abs_diff_eq!(c, 0.16914122022145014, epsilon = EPS);
+  assert_abs_diff_eq!(d, 1.5002142800592426, epsilon = EPS);
 
   // A matrix with a low condition number, which is a bad case for the base QR algorithm
   let delta = 1e-5;
-  let [a, b, c, d] = sorted(
+  let [a, b, c, d] = DMat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, delta, 0.0, 0.0, delta, 1.0,
+  ])
+  .eigvals_symmetric();
+  assert_abs_diff_eq!(a, 1.0 - delta, epsilon = EPS);
+  assert_abs_diff_eq!(b, 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(c, 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(d, 1.0 + delta, epsilon = EPS);
+
+  // Nearly repeated eigenvalues, which the general QR algorithm can split into a complex pair
+  let delta = 1e-9;
+  let [a, b, c, d] = DMat4::from_cols_array(&[
+    2.0, delta, 0.0, 0.0, delta, 2.0, delta, 0.0, 0.0, delta, 2.0, delta, 0.0, 0.0, delta, 2.0,
+  ])
+  .eigvals_symmetric();
+  let golden = 0.5 * (1.0 + 5f64.sqrt());
+  assert_abs_diff_eq!(a, 2.0 - golden * delta, epsilon = EPS);
+  assert_abs_diff_eq!(b, 2.0 - (golden - 1.0) * delta, epsilon = EPS);
+  assert_abs_diff_eq!(c, 2.0 + (golden - 1.0) * delta, epsilon = EPS);
+  assert_abs_diff_eq!(d, 2.0 + golden * delta, epsilon = EPS);
+}
+
+#[test]
+fn test_eigvals4_symmetric_scaled() {
+  let A = DMat4::from_cols_array(&[
+    4.0, 1.0, 0.0, 2.0, 1.0, 4.0, 1.0, 0.0, 0.0, 1.0, 4.0, 1.0, 2.0, 0.0, 1.0, 4.0,
+  ]);
+  let expected = A.eigvals_symmetric();
+
+  for scale in [1e-300, 1e-170, 1e-160, 1e155, 1e300] {
+    let eigvals = (scale * A).eigvals_symmetric();
+    for (lambda, expected) in eigvals.into_iter().zip(expected) {
+      assert_abs_diff_eq!(lambda / scale, expected, epsilon = EPS);
+    }
+  }
+  assert_eq!(DMat4::ZERO.eigvals_symmetric(), [0.0; 4]);
+}
+
+#[test]
+fn test_eigvals4_scaled() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.5,
+  ]);
+  let expected = A.eigvals();
+  let radius = A.spectral_radius();
+
+  for scale in [1e-300, 1e-170, 1e150, 1e300] {
+    let eigvals = (scale * A).eigvals();
+    for (lambda, expected) in eigvals.into_iter().zip(expected) {
+      assert_complex_eq(lambda / scale, expected, EPS);
+    }
+    assert_abs_diff_eq!((scale * A).spectral_radius() / scale, radius, epsilon = EPS);
+  }
+}
+
+#[test]
+fn test_eig4() {
+  for A in [
+    DMat4::from_cols_array(&[
+      1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+    ]),
+    DMat4::from_cols_array(&[
+      0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 0.0,
+    ]),
+    DMat4::from_cols_array(&[
+      2.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 3.0,
+    ]),
+    DMat4::from_cols_array(&[
+      1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    ]),
+  ] {
+    let (eigvals, re, im) = A.eig();
+    assert_eigvecs(
+      A.to_cols_array_2d(),
+      &eigvals,
+      re.to_cols_array_2d(),
+      im.to_cols_array_2d(),
+      EPS,
+    );
+  }
+
+  let (_, re, _) = DMat4::from_diagonal(dvec4(2.0, 2.0, 3.0, 3.0)).eig();
+  assert!(re.determinant().abs() > 0.5);
+}
+
+#[test]
+fn test_eig4_symmetric() {
+  let delta = 1e-5;
+  for A in [
     DMat4::from_cols_array(&[
       1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, delta, 0.0, 0.0, delta, 1.0,
-    ])
-    .eigvals_symmetric(),
-  );
-  assert_abs_diff_eq!(a, 1.0 - delta, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(b, 1.0, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(c, 1.0, epsilon = SYMMETRIC_EPS);
-  assert_abs_diff_eq!(d, 1.0 + delta, epsilon = SYMMETRIC_EPS);
+    ]),
+    DMat4::from_cols_array(&[
+      4.0, 1.0, 0.0, 0.0, 1.0, 4.0, 1.0, 0.0, 0.0, 1.0, 4.0, 1.0, 0.0, 0.0, 1.0, 4.0,
+    ]),
+    // Repeated eigenvalues
+    DMat4::from_diagonal(dvec4(2.0, 2.0, 3.0, 3.0)),
+    DMat4::from_cols_array(&[
+      1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+    ]),
+  ] {
+    let (eigvals, V) = A.eig_symmetric();
+    let D = DMat4::from_diagonal(DVec4::from_array(eigvals));
+    assert!((V * D * V.transpose()).abs_diff_eq(A, EPS));
+    assert!((V.transpose() * V).abs_diff_eq(DMat4::IDENTITY, EPS));
+  }
+}
+
+#[test]
+fn test_eig4_symmetric_scaled() {
+  // The Jacobi threshold must not overflow or underflow at extreme scales
+  let A = DMat4::from_cols_array(&[
+    4.0, 1.0, 0.0, 2.0, 1.0, 4.0, 1.0, 0.0, 0.0, 1.0, 4.0, 1.0, 2.0, 0.0, 1.0, 4.0,
+  ]);
+  let (expected, _) = A.eig_symmetric();
+
+  for scale in [1e-160, 1e160] {
+    let (eigvals, V) = (scale * A).eig_symmetric();
+    for (lambda, expected) in eigvals.into_iter().zip(expected) {
+      assert_abs_diff_eq!(lambda / scale, expected, epsilon = EPS);
+    }
+    let D = DMat4::from_diagonal(DVec4::from_array(eigvals) / scale);
+    assert!((V * D * V.transpose()).abs_diff_eq(A, EPS));
+    assert!((V.transpose() * V).abs_diff_eq(DMat4::IDENTITY, EPS));
+  }
 }
 
 test_dmat_rand!(4);
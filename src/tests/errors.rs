@@ -0,0 +1,92 @@
+use crate::*;
+
+use glam::{DMat2, DMat3, DMat4, Mat3};
+
+#[test]
+fn test_non_finite_input() {
+  let err = LinalgError::NonFiniteInput;
+
+  let A = DMat2::from_cols_array(&[1.0, f64::NAN, 0.0, 1.0]);
+  assert_eq!(A.try_eigvals().unwrap_err(), err);
+  assert_eq!(A.try_eigvals_symmetric().unwrap_err(), err);
+
+  let A = DMat3::from_cols_array(&[1.0, 0.0, 0.0, 0.0, f64::INFINITY, 0.0, 0.0, 0.0, 1.0]);
+  assert_eq!(A.try_eigvals().unwrap_err(), err);
+  assert_eq!(A.try_eigvals_symmetric().unwrap_err(), err);
+
+  let A = DMat4::IDENTITY * f64::NAN;
+  assert_eq!(A.try_eigvals().unwrap_err(), err);
+  assert_eq!(A.try_eigvals_symmetric().unwrap_err(), err);
+
+  let A = Mat3::IDENTITY * f32::NEG_INFINITY;
+  assert_eq!(A.try_eigvals().unwrap_err(), err);
+}
+
+#[test]
+fn test_no_convergence() {
+  // A matrix without a negligible subdiagonal entry cannot deflate in only two iterations
+  let A = DMat3::from_cols_array(&[1.0, 2.0, 3.0, -4.0, 5.0, 6.0, 7.0, 8.0, -9.0]);
+  let options = EigenOptions::default().with_max_iterations(2);
+  assert_eq!(
+    A.eigvals_with(&options),
+    Err(LinalgError::NoConvergence { iterations: 2 })
+  );
+  assert_eq!(
+    LinalgError::NoConvergence { iterations: 2 }.to_string(),
+    "failed to converge after 2 iterations"
+  );
+}
+
+#[test]
+fn test_eigvals_non_finite() {
+  // The infallible methods give NaN for non-finite input at every size
+  for x in [f64::NAN, f64::INFINITY] {
+    let is_nan = |lambda: &Complex64| lambda.is_nan();
+    assert!((DMat2::IDENTITY * x).eigvals().iter().all(is_nan));
+    assert!((DMat3::IDENTITY * x).eigvals().iter().all(is_nan));
+    assert!((DMat4::IDENTITY * x).eigvals().iter().all(is_nan));
+
+    assert!((DMat2::IDENTITY * x)
+      .eigvals_symmetric()
+      .iter()
+      .all(|x| x.is_nan()));
+    assert!((DMat3::IDENTITY * x)
+      .eigvals_symmetric()
+      .iter()
+      .all(|x| x.is_nan()));
+    assert!((DMat4::IDENTITY * x)
+      .eigvals_symmetric()
+      .iter()
+      .all(|x| x.is_nan()));
+
+    let (eigvals, re, im) = (DMat2::IDENTITY * x).eig();
+    assert!(eigvals.iter().all(is_nan) && re.is_nan() && im.is_nan());
+    let (eigvals, re, im) = (DMat3::IDENTITY * x).eig();
+    assert!(eigvals.iter().all(is_nan) && re.is_nan() && im.is_nan());
+    let (eigvals, re, im) = (DMat4::IDENTITY * x).eig();
+    assert!(eigvals.iter().all(is_nan) && re.is_nan() && im.is_nan());
+  }
+}
+
+#[test]
+fn test_eig_symmetric_non_finite() {
+  for x in [f64::NAN, f64::INFINITY] {
+    let (eigvals, V) = (DMat2::IDENTITY * x).eig_symmetric();
+    assert!(eigvals.iter().all(|x| x.is_nan()) && V.is_nan());
+    let (eigvals, V) = (DMat3::IDENTITY * x).eig_symmetric();
+    assert!(eigvals.iter().all(|x| x.is_nan()) && V.is_nan());
+    let (eigvals, V) = (DMat4::IDENTITY * x).eig_symmetric();
+    assert!(eigvals.iter().all(|x| x.is_nan()) && V.is_nan());
+  }
+}
+
+#[test]
+fn test_try_eigvals() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ]);
+  assert_eq!(A.try_eigvals(), Ok(A.eigvals()));
+
+  let A = A + A.transpose();
+  assert_eq!(A.try_eigvals_symmetric(), Ok(A.eigvals_symmetric()));
+}
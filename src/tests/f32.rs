@@ -0,0 +1,167 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{vec3, vec4, Mat2, Mat3, Mat3A, Mat4, Quat, Vec3, Vec4};
+
+const EPS_F32: f32 = 1e-5;
+
+#[test]
+fn test_trace_f32() {
+  assert_eq!(Mat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]).trace(), 5.0);
+  let A = Mat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+  assert_eq!(A.trace(), 15.0);
+  assert_eq!(Mat3A::from(A).trace(), 15.0);
+  assert_eq!(Mat4::IDENTITY.trace(), 4.0);
+}
+
+#[test]
+fn test_eigvals_f32() {
+  let [a, b] = Mat2::from_cols_array(&[1.0, -1.0, 1.0, 1.0]).eigvals();
+  assert!(a.abs_diff_eq(Complex32::new(1.0, -1.0), EPS_F32));
+  assert!(b.abs_diff_eq(Complex32::new(1.0, 1.0), EPS_F32));
+
+  // The f32 results should agree with the f64 results
+  let A = Mat3::from_cols_array(&[1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+  let expected = A.as_dmat3().eigvals();
+  for eigvals in [A.eigvals(), Mat3A::from(A).eigvals()] {
+    let eigvals = eigvals.map(|x| x.as_complex64());
+    for (a, b) in eigvals.into_iter().zip(expected) {
+      assert_complex_eq(a, b, EPS_F32 as f64);
+    }
+  }
+
+  let A = Mat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+  ]);
+  let eigvals = A.eigvals().map(|x| x.as_complex64());
+  let expected = A.as_dmat4().eigvals();
+  for (a, b) in eigvals.into_iter().zip(expected) {
+    assert_complex_eq(a, b, 1e2 * EPS_F32 as f64);
+  }
+}
+
+#[test]
+fn test_eig_f32() {
+  let A = Mat3A::from_cols_array(&[1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+  let (eigvals, re, im) = A.eig();
+  for (k, lambda) in eigvals.into_iter().enumerate() {
+    let (re, im) = (re.col(k), im.col(k));
+    assert!((A * re).abs_diff_eq(lambda.re * re - lambda.im * im, EPS_F32));
+    assert!((A * im).abs_diff_eq(lambda.re * im + lambda.im * re, EPS_F32));
+  }
+}
+
+#[test]
+fn test_eig_symmetric_f32() {
+  let A = Mat3::from_cols_array(&[2.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 3.0]);
+  let [a, b, c] = A.eigvals_symmetric().map(f64::from);
+  assert_abs_diff_eq!(a, 1.0, epsilon = EPS_F32 as f64);
+  assert_abs_diff_eq!(b, 3.0, epsilon = EPS_F32 as f64);
+  assert_abs_diff_eq!(c, 3.0, epsilon = EPS_F32 as f64);
+
+  let (eigvals, V) = A.eig_symmetric();
+  let D = Mat3::from_diagonal(Vec3::from_array(eigvals));
+  assert!((V * D * V.transpose()).abs_diff_eq(A, EPS_F32));
+
+  let A = Mat4::from_diagonal(vec4(1.0, 2.0, 3.0, 4.0))
+    + Mat4::from_cols(Vec4::ONE, Vec4::ONE, Vec4::ONE, Vec4::ONE);
+  let (eigvals, V) = A.eig_symmetric();
+  let D = Mat4::from_diagonal(Vec4::from_array(eigvals));
+  assert!((V * D * V.transpose()).abs_diff_eq(A, EPS_F32));
+  assert!((V.transpose() * V).abs_diff_eq(Mat4::IDENTITY, EPS_F32));
+}
+
+#[test]
+fn test_norms_f32() {
+  let A = Mat2::from_cols_array(&[1.0, -3.0, 2.0, 4.0]);
+  assert_eq!(A.norm1(), 6.0);
+  assert_eq!(A.norm_inf(), 7.0);
+  assert_eq!(A.norm_max(), 4.0);
+  assert_abs_diff_eq!(A.norm_frobenius(), 30f32.sqrt(), epsilon = EPS_F32);
+  assert_abs_diff_eq!(A.norm2(), (15.0 + 125f32.sqrt()).sqrt(), epsilon = EPS_F32);
+  assert_abs_diff_eq!(A.cond1(), 6.0 * 0.7, epsilon = EPS_F32);
+  assert_abs_diff_eq!(A.spectral_radius(), 10f32.sqrt(), epsilon = EPS_F32);
+
+  let A = Mat3A::from_diagonal(vec3(2.0, -8.0, 0.5));
+  assert_eq!(A.norm2(), 8.0);
+  assert_eq!(A.cond1(), 16.0);
+  assert_eq!(A.cond2(), 16.0);
+  assert_eq!(A.spectral_radius(), 8.0);
+
+  let Q = Mat4::from_quat(Quat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0));
+  assert_abs_diff_eq!(Q.norm2(), 1.0, epsilon = EPS_F32);
+  assert_abs_diff_eq!(Q.cond2(), 1.0, epsilon = EPS_F32);
+  assert_abs_diff_eq!(Q.norm_frobenius(), 2.0, epsilon = EPS_F32);
+
+  let v = vec3(1.0, -2.0, 0.5);
+  let A = Mat3::from_cols(v, 2.0 * v, -v);
+  assert_eq!(A.cond1(), f32::INFINITY);
+  assert_eq!(A.cond2(), f32::INFINITY);
+
+  // The Frobenius norm of large f32 matrices does not overflow
+  assert_abs_diff_eq!(
+    (Mat2::IDENTITY * 1e30).norm_frobenius(),
+    2f32.sqrt() * 1e30,
+    epsilon = 1e25
+  );
+}
+
+#[test]
+fn test_predicates_f32() {
+  let Q = Mat3::from_quat(Quat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0));
+  assert!(Q.is_orthogonal(EPS_F32));
+  assert!(Q.is_rotation(EPS_F32));
+  assert!(!(-Q).is_rotation(EPS_F32));
+  assert!(Mat3A::from(Q).is_orthogonal(EPS_F32));
+
+  let A = Mat4::from_diagonal(vec4(1.0, 2.0, 3.0, 4.0))
+    + Mat4::from_cols(Vec4::ONE, Vec4::ONE, Vec4::ONE, Vec4::ONE);
+  assert!(A.is_symmetric(0.0));
+  assert!(A.is_positive_definite(EPS_F32));
+  assert!(!(-A).is_positive_definite(EPS_F32));
+  assert!(!A.is_diagonal(EPS_F32));
+  assert!((A - A.transpose()).is_skew_symmetric(0.0));
+
+  let A = Mat3::from_cols_array(&[1.0, 2.0, 0.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+  assert!(A.is_hessenberg(0.0));
+  assert!(!A.is_upper_triangular(0.0));
+  assert!(!A.transpose().is_hessenberg(0.0));
+}
+
+#[test]
+fn test_eigvals_with_f32() {
+  let A = Mat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ]);
+
+  // The default tolerance is raised to the f32 machine epsilon, so the QR iteration still converges
+  let (eigvals, diagnostics) = A.eigvals_with(&EigenOptions::default()).unwrap();
+  assert_eq!(eigvals, A.eigvals());
+  let [a, b, c] = diagnostics.subdiagonal().try_into().unwrap();
+  assert!(a.min(c) <= f32::EPSILON as f64 * A.norm_max() as f64);
+  assert!(b > 0.1);
+}
+
+#[test]
+fn test_eigvals_f32_rand() {
+  let mut rng = Rng::with_seed(SEED);
+
+  for _ in 0..N_TESTS / 10 {
+    let v: Vec<_> = (0..16).map(|_| rng.f32()).collect();
+    let A = Mat4::from_cols_slice(&v);
+    let eigvals = A.eigvals().map(|x| x.as_complex64());
+    let A = A.as_dmat4();
+    assert_valid(
+      &eigvals,
+      A.trace(),
+      (A * A).trace(),
+      A.determinant(),
+      1e3 * EPS_F32 as f64,
+    );
+
+    let A = Mat3::from_cols_slice(&v[..9]);
+    assert_eq!(A.eigvals(), Mat3A::from(A).eigvals());
+  }
+}
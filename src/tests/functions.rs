@@ -0,0 +1,340 @@
+use crate::functions::{exp_pade, SquareMatrix};
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec2, dvec3, DMat2, DMat3, DMat4, DVec3};
+use paste::paste;
+
+/// Asserts that exp(A) satisfies the identities of the matrix exponential, relative to its magnitude
+macro_rules! assert_exp {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let E = A.exp();
+      let scale = E.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()));
+
+      // exp(A) commutes with A
+      let norm = A.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()));
+      assert!((E * A).abs_diff_eq(A * E, 10.0 * EPS * scale * norm));
+
+      // The determinant is e^tr(A) and exp(A) exp(-A) = I, as long as neither is dominated by rounding
+      if norm <= 1.0 {
+        assert_abs_diff_eq!(E.determinant().ln(), A.trace(), epsilon = 1e-9);
+        assert!((E * (-A).exp()).abs_diff_eq([< DMat $num >]::IDENTITY, 1e-9));
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_exp() {
+  for A in [DMat2::ZERO, DMat2::from_diagonal(dvec2(1.0, -2.0))] {
+    assert_exp!(2, A);
+  }
+  assert_eq!(DMat3::ZERO.exp(), DMat3::IDENTITY);
+  assert_eq!(DMat4::ZERO.exp(), DMat4::IDENTITY);
+
+  // Diagonal matrices exponentiate entrywise
+  let E = DMat3::from_diagonal(dvec3(1.0, -2.0, 0.5)).exp();
+  assert!(E.abs_diff_eq(
+    DMat3::from_diagonal(dvec3(1f64.exp(), (-2f64).exp(), 0.5f64.exp())),
+    EPS
+  ));
+  // The accuracy is relative to the norm of the result
+  let E = DMat2::from_diagonal(dvec2(-50.0, 50.0)).exp() * (-50f64).exp();
+  assert!(E.abs_diff_eq(DMat2::from_diagonal(dvec2(0.0, 1.0)), EPS));
+
+  // The 2x2 closed form keeps the components along the smaller eigenvalue to full relative accuracy
+  let (p, m) = (50f64.exp(), (-50f64).exp());
+  let E = DMat2::from_cols_array(&[-50.0, 0.0, 1.0, 50.0]).exp();
+  assert_abs_diff_eq!(E.x_axis.x / m, 1.0, epsilon = EPS);
+  assert_eq!(E.x_axis.y, 0.0);
+  assert_abs_diff_eq!(E.y_axis.x / ((p - m) / 100.0), 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(E.y_axis.y / p, 1.0, epsilon = EPS);
+
+  // The series of a nilpotent matrix is finite
+  let N = DMat3::from_cols_array(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 3.0, 0.0]);
+  let E = DMat3::IDENTITY + N + 0.5 * N * N;
+  assert!(N.exp().abs_diff_eq(E, EPS));
+  let N = DMat2::from_cols_array(&[0.0, 0.0, 1.0, 0.0]);
+  assert_eq!(N.exp(), DMat2::from_cols_array(&[1.0, 0.0, 1.0, 1.0]));
+
+  // Skew-symmetric matrices exponentiate to rotations
+  let E = DMat2::from_cols_array(&[0.0, 1.0, -1.0, 0.0]).exp();
+  assert!(E.abs_diff_eq(DMat2::from_angle(1.0), EPS));
+  let omega = dvec3(0.3, -1.2, 2.0);
+  let A = DMat3::from_cols(
+    dvec3(0.0, omega.z, -omega.y),
+    dvec3(-omega.z, 0.0, omega.x),
+    dvec3(omega.y, -omega.x, 0.0),
+  );
+  let R = DMat3::from_axis_angle(omega.normalize(), omega.length());
+  assert!(A.exp().abs_diff_eq(R, EPS));
+  assert!(exp_pade(A).abs_diff_eq(R, EPS));
+  assert!((A * 1e-9)
+    .exp()
+    .abs_diff_eq(DMat3::IDENTITY + A * 1e-9, EPS));
+
+  // Large norms are scaled and squared
+  let A = DMat4::from_cols_array(&[
+    -30.0, 2.0, 0.0, 1.0, 4.0, -20.0, 1.0, 0.0, 0.0, 3.0, -25.0, 2.0, 1.0, 0.0, 5.0, -40.0,
+  ]);
+  assert_exp!(4, A);
+  assert!(A.exp().abs_diff_eq(DMat4::ZERO, 1e-6));
+  assert_exp!(
+    3,
+    DMat3::from_diagonal(DVec3::splat(100.0)) + DMat3::from_cols_array(&[1.0; 9])
+  );
+}
+
+#[test]
+fn test_exp_non_finite() {
+  for x in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+    assert!(!(DMat2::IDENTITY * x).exp().is_finite());
+    assert!(!(DMat3::IDENTITY * x).exp().is_finite());
+    assert!(!(DMat4::IDENTITY * x).exp().is_finite());
+
+    let mut A = DMat4::IDENTITY;
+    A.z_axis.y = x;
+    assert!(exp_pade(A).is_nan());
+  }
+}
+
+/// Asserts that sqrt(A)² = A and exp(log(A)) = A
+macro_rules! assert_log_sqrt {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let norm = A.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()));
+
+      let S = A.sqrt().unwrap();
+
+      assert!((S * S).abs_diff_eq(A, 1e-10 * norm));
+      assert!(S.eigvals().iter().all(|lambda| lambda.re > 0.0));
+
+      let L = A.log().unwrap();
+      assert!(L.exp().abs_diff_eq(A, 1e-10 * norm));
+      assert!(L.eigvals().iter().all(|lambda| lambda.im.abs() < std::f64::consts::PI));
+    }}
+  };
+}
+
+#[test]
+fn test_log_sqrt() {
+  assert_eq!(DMat3::IDENTITY.log(), Ok(DMat3::ZERO));
+  assert_eq!(DMat4::IDENTITY.sqrt(), Ok(DMat4::IDENTITY));
+
+  let A = DMat3::from_diagonal(dvec3(4.0, 0.25, 1e6));
+  assert!(A
+    .sqrt()
+    .unwrap()
+    .abs_diff_eq(DMat3::from_diagonal(dvec3(2.0, 0.5, 1e3)), EPS));
+  let L = DMat3::from_diagonal(dvec3(4f64.ln(), 0.25f64.ln(), 1e6f64.ln()));
+  assert!(A.log().unwrap().abs_diff_eq(L, EPS));
+  assert_log_sqrt!(3, A);
+
+  // Rotations by less than π have skew-symmetric logarithms
+  let R = DMat2::from_angle(3.0);
+  assert!(R
+    .log()
+    .unwrap()
+    .abs_diff_eq(DMat2::from_cols_array(&[0.0, 3.0, -3.0, 0.0]), 1e-10));
+  assert!(R.sqrt().unwrap().abs_diff_eq(DMat2::from_angle(1.5), 1e-10));
+  let axis = dvec3(0.3, -1.2, 2.0).normalize();
+  let L = DMat3::from_axis_angle(axis, 2.5).log().unwrap();
+  assert!(L.abs_diff_eq(-L.transpose(), 1e-10));
+  assert!(L
+    .exp()
+    .abs_diff_eq(DMat3::from_axis_angle(axis, 2.5), 1e-10));
+
+  // A Jordan block with a positive eigenvalue
+  let J = DMat4::from_cols_array(&[
+    2.0, 0.0, 0.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0, 1.0, 2.0,
+  ]);
+  assert_log_sqrt!(4, J);
+
+  // Eigenvalues on the closed negative real axis have no principal logarithm or square root
+  let err = LinalgError::NegativeRealEigenvalue;
+  let A = DMat2::from_diagonal(dvec2(-1.0, 1.0));
+  assert_eq!(A.log().unwrap_err(), err);
+  assert_eq!(A.sqrt().unwrap_err(), err);
+  assert_eq!((-DMat2::IDENTITY).log().unwrap_err(), err);
+  assert_eq!(DMat3::ZERO.sqrt().unwrap_err(), err);
+  let v = dvec3(1.0, -2.0, 0.5);
+  assert_eq!(DMat3::from_cols(v, 2.0 * v, -v).log().unwrap_err(), err);
+  assert_eq!(
+    DMat4::from_diagonal((1.0, 2.0, -1e-3, 3.0).into())
+      .sqrt()
+      .unwrap_err(),
+    err
+  );
+
+  // Small positive eigenvalues are not on the negative real axis, however ill-conditioned the matrix
+  // nor do they cancel to zero in the closed form 2x2 eigenvalues, including deflated 2x2 blocks
+  let A = DMat2::from_diagonal(dvec2(1.0, 1e-20));
+  assert!(A
+    .log()
+    .unwrap()
+    .abs_diff_eq(DMat2::from_diagonal(dvec2(0.0, 1e-20f64.ln())), 1e-10));
+  assert!(A
+    .sqrt()
+    .unwrap()
+    .abs_diff_eq(DMat2::from_diagonal(dvec2(1.0, 1e-10)), EPS));
+  let A = DMat3::from_diagonal(dvec3(4.0, 1.0, 1e-18));
+  assert!(A
+    .sqrt()
+    .unwrap()
+    .abs_diff_eq(DMat3::from_diagonal(dvec3(2.0, 1.0, 1e-9)), EPS));
+  assert!(A.log().is_ok());
+
+  let A = DMat3::from_diagonal(dvec3(1e-8, 1.0, 1e8));
+  let L = A.log().unwrap();
+  assert!(L.abs_diff_eq(
+    DMat3::from_diagonal(dvec3(-8.0, 0.0, 8.0) * 10f64.ln()),
+    1e-10
+  ));
+  let S = A.sqrt().unwrap();
+  assert!((S * 1e4f64.recip()).abs_diff_eq(DMat3::from_diagonal(dvec3(1e-8, 1e-4, 1.0)), EPS));
+  let S = DMat3::from_diagonal(dvec3(1e-300, 1.0, 1e8))
+    .sqrt()
+    .unwrap();
+  assert_abs_diff_eq!(S.x_axis.x / 1e-150, 1.0, epsilon = EPS);
+
+  let A = DMat3::IDENTITY * f64::NAN;
+  assert_eq!(A.log(), Err(LinalgError::NonFiniteInput));
+  assert_eq!(A.sqrt(), Err(LinalgError::NonFiniteInput));
+}
+
+macro_rules! test_log_sqrt_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_log_sqrt $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS / 10 {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+
+          // Exponentials of small matrices have principal logarithms that recover them
+          let E = A.exp();
+          assert!(E.log().unwrap().abs_diff_eq(A, 1e-10));
+          assert_log_sqrt!($num, E);
+
+          if A.check_principal().is_ok() {
+            assert_log_sqrt!($num, A);
+          }
+        }
+      }
+    }
+  };
+}
+
+test_log_sqrt_rand!(2);
+test_log_sqrt_rand!(3);
+test_log_sqrt_rand!(4);
+
+macro_rules! test_exp_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_exp $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for i in 0..N_TESTS / 10 {
+          // The norms range from 1e-8 to 1e2, covering every Padé degree and the scaled case
+          let scale = 10f64.powi((i % 11) as i32 - 8);
+          let v: Vec<_> = (0..$num * $num).map(|_| scale * (rng.f64() - 0.5)).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          assert_exp!($num, A);
+
+          // The closed form agrees with the Padé approximant, and both agree with nalgebra
+          let E = exp_pade(A);
+          let reference = nalgebra::[< Matrix $num >]::from_column_slice(&v).exp();
+          let reference = [< DMat $num >]::from_cols_slice(reference.as_slice());
+          assert!(reference.abs_diff_eq(E, 1e-9 * E.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()))));
+          assert!(A.exp().abs_diff_eq(E, 1e-9 * E.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()))));
+        }
+      }
+    }
+  };
+}
+
+test_exp_rand!(2);
+test_exp_rand!(3);
+test_exp_rand!(4);
+
+/// Asserts the identities of the spectral functions on a symmetric positive definite matrix
+macro_rules! assert_spectral {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let norm = A.to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()));
+
+      assert!(A.map_symmetric(|lambda| lambda).abs_diff_eq(A, EPS * norm));
+      let S = A.sqrt_spd();
+      assert_eq!(S, S.transpose());
+      assert!((S * S).abs_diff_eq(A, 1e-10 * norm));
+      assert!(S.abs_diff_eq(A.sqrt().unwrap(), 1e-10 * norm));
+      assert!((A.inv_sqrt_spd() * S).abs_diff_eq([< DMat $num >]::IDENTITY, 1e-8));
+      assert!(A.log_spd().exp().abs_diff_eq(A, 1e-10 * norm));
+      assert!(A.powf_spd(2.0).abs_diff_eq(A * A, 1e-10 * norm * norm));
+      assert!(A.powf_spd(-1.0).abs_diff_eq(A.inverse(), 1e-8 * A.inverse().to_cols_array().iter().fold(1.0_f64, |max, x| max.max(x.abs()))));
+      assert!(A.project_psd().abs_diff_eq(A, EPS * norm));
+    }}
+  };
+}
+
+#[test]
+fn test_spectral() {
+  let A = DMat3::from_cols_array(&[4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]);
+  assert_spectral!(3, A);
+  let A = DMat2::from_diagonal(dvec2(4.0, 9.0));
+  assert!(A
+    .sqrt_spd()
+    .abs_diff_eq(DMat2::from_diagonal(dvec2(2.0, 3.0)), EPS));
+  assert_spectral!(2, A);
+
+  // The projection clamps the negative eigenvalues
+  let A = DMat2::from_cols_array(&[0.0, 1.0, 1.0, 0.0]);
+  let P = A.project_psd();
+  assert!(P.abs_diff_eq(DMat2::from_cols_array(&[0.5, 0.5, 0.5, 0.5]), EPS));
+  assert!(DMat3::from_diagonal(dvec3(-1.0, 2.0, -3.0))
+    .project_psd()
+    .abs_diff_eq(DMat3::from_diagonal(dvec3(0.0, 2.0, 0.0)), EPS));
+
+  // Arbitrary functions, here the sign function
+  let A = DMat4::from_diagonal((3.0, -2.0, 1.0, -0.5).into());
+  let R = DMat4::from_quat(glam::DQuat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0));
+  let M = (R * A * R.transpose()).map_symmetric(f64::signum);
+  let expected = R * DMat4::from_diagonal((1.0, -1.0, 1.0, -1.0).into()) * R.transpose();
+  assert!(M.abs_diff_eq(expected, EPS));
+}
+
+macro_rules! test_spectral_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_spectral $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS / 10 {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let M = [< DMat $num >]::from_cols_slice(&v);
+          assert_spectral!($num, M * M.transpose() + 1e-2 * [< DMat $num >]::IDENTITY);
+
+          // Projections are positive semidefinite and idempotent
+          let P = (M + M.transpose()).project_psd();
+          assert!(P.eigvals_symmetric().iter().all(|&lambda| lambda >= -EPS));
+          assert!(P.project_psd().abs_diff_eq(P, 1e-10));
+        }
+      }
+    }
+  };
+}
+
+test_spectral_rand!(2);
+test_spectral_rand!(3);
+test_spectral_rand!(4);
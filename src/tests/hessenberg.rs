@@ -0,0 +1,102 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+
+use fastrand::Rng;
+use glam::{dvec3, dvec4, DMat2, DMat3, DMat4};
+use paste::paste;
+
+/// Asserts that (Q, H) is a valid hessenberg decomposition of A
+macro_rules! assert_hessenberg {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (Q, H) = A.hessenberg();
+      assert!((Q * H * Q.transpose()).abs_diff_eq(A, EPS));
+      assert!((Q.transpose() * Q).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+
+      let H = H.to_cols_array_2d();
+      for j in 0..$num {
+        for i in j + 2..$num {
+          assert_eq!(H[j][i], 0.0);
+        }
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_hessenberg() {
+  assert_hessenberg!(2, DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
+  assert_hessenberg!(
+    3,
+    DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0])
+  );
+  assert_hessenberg!(3, DMat3::ZERO);
+  assert_hessenberg!(4, DMat4::from_cols_array(&[1.0; 16]));
+  assert_hessenberg!(4, DMat4::ZERO);
+
+  // A matrix already in hessenberg form, with zero subdiagonal entries to reflect
+  assert_hessenberg!(
+    3,
+    DMat3::from_cols(
+      dvec3(1.0, 0.0, 0.0),
+      dvec3(2.0, 3.0, 0.0),
+      dvec3(4.0, 5.0, 6.0)
+    )
+  );
+
+  // The hessenberg form of a symmetric matrix is tridiagonal
+  let A = DMat4::from_cols(
+    dvec4(4.0, 1.0, -2.0, 2.0),
+    dvec4(1.0, 2.0, 0.0, 1.0),
+    dvec4(-2.0, 0.0, 3.0, -2.0),
+    dvec4(2.0, 1.0, -2.0, -1.0),
+  );
+  let (_, H) = A.hessenberg();
+  assert!(H.abs_diff_eq(H.transpose(), EPS));
+  assert_abs_diff_eq!(H.z_axis.x, 0.0, epsilon = EPS);
+  assert_abs_diff_eq!(H.w_axis.x, 0.0, epsilon = EPS);
+  assert_abs_diff_eq!(H.w_axis.y, 0.0, epsilon = EPS);
+  assert_eq!(H.x_axis.x, 4.0);
+  assert_abs_diff_eq!(H.x_axis.y.abs(), 3.0, epsilon = EPS);
+}
+
+#[test]
+fn test_hessenberg_scaled() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.5,
+  ]);
+
+  // The reconstruction should be accurate relative to A even where the squared entries overflow or underflow
+  for scale in [1e200, 1e-170, 1e-200] {
+    let A = A * scale;
+    let (Q, H) = A.hessenberg();
+    assert!((Q * H * Q.transpose() * scale.recip()).abs_diff_eq(A * scale.recip(), EPS));
+    assert!((Q.transpose() * Q).abs_diff_eq(DMat4::IDENTITY, EPS));
+
+    let A = DMat3::from_mat4(A);
+    let (Q, H) = A.hessenberg();
+    assert!((Q * H * Q.transpose() * scale.recip()).abs_diff_eq(A * scale.recip(), EPS));
+  }
+}
+
+macro_rules! test_hessenberg_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_hessenberg $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          assert_hessenberg!($num, [< DMat $num >]::from_cols_slice(&v));
+        }
+      }
+    }
+  };
+}
+
+test_hessenberg_rand!(3);
+test_hessenberg_rand!(4);
@@ -0,0 +1,108 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec2, dvec3, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+use paste::paste;
+
+#[test]
+fn test_least_squares() {
+  // Fitting a line y = 2 t + 1 exactly
+  let mut lsq = LeastSquares::<2>::new();
+  for t in 0..10 {
+    let t = t as f64;
+    lsq.add_row(dvec2(t, 1.0), 2.0 * t + 1.0);
+  }
+  assert_eq!(lsq.rows(), 10);
+  assert_eq!(lsq.rank(), 2);
+  assert!(lsq.solve().unwrap().abs_diff_eq(dvec2(2.0, 1.0), EPS));
+  assert_abs_diff_eq!(lsq.residual_norm(), 0.0, epsilon = EPS);
+  assert!(lsq.covariance().unwrap().abs_diff_eq(DMat2::ZERO, EPS));
+
+  // The mean of the targets, with residual variance 1
+  let mut lsq = LeastSquares::<2>::new();
+  for y in [1.0, 2.0, 3.0] {
+    lsq.add_row(dvec2(1.0, 0.0), y);
+    assert_eq!(lsq.rank(), 1);
+  }
+  assert!(lsq.is_rank_deficient());
+  assert_eq!(lsq.solve(), None);
+  assert_eq!(lsq.covariance(), None);
+  lsq.add_row(dvec2(0.0, 1.0), 5.0);
+  assert!(lsq.solve().unwrap().abs_diff_eq(dvec2(2.0, 5.0), EPS));
+  assert_abs_diff_eq!(lsq.residual_norm(), 2f64.sqrt(), epsilon = EPS);
+  let C = lsq.covariance().unwrap();
+  assert!(C.abs_diff_eq(DMat2::from_diagonal(dvec2(1.0 / 3.0, 1.0)), EPS));
+
+  // Too few rows and parallel rows are rank deficient
+  let mut lsq = LeastSquares::<3>::new();
+  assert_eq!(lsq.rank(), 0);
+  assert_eq!(lsq.solve(), None);
+  let v = dvec3(1.0, -2.0, 0.5);
+  for k in 1..10 {
+    lsq.add_row(k as f64 * v, 1.0);
+  }
+  assert_eq!(lsq.rank(), 1);
+  lsq.add_row(DVec3::X, 1.0);
+  assert_eq!(lsq.rank(), 2);
+  assert_eq!(lsq.solve(), None);
+
+  // A weighted row counts as that many repeated rows
+  let mut a = LeastSquares::<2>::new();
+  let mut b = LeastSquares::<2>::new();
+  for (row, y) in [
+    (dvec2(1.0, 2.0), 1.0),
+    (dvec2(-1.0, 0.5), 2.0),
+    (dvec2(3.0, 1.0), 0.0),
+  ] {
+    a.add_weighted_row(row, y, 3.0);
+    (0..3).for_each(|_| b.add_row(row, y));
+  }
+  assert!(a.solve().unwrap().abs_diff_eq(b.solve().unwrap(), EPS));
+  assert_abs_diff_eq!(a.residual_norm(), b.residual_norm(), epsilon = EPS);
+}
+
+macro_rules! test_least_squares_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_least_squares $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS / 10 {
+          let mut lsq = LeastSquares::<$num>::new();
+          let mut AtA = [< DMat $num >]::ZERO;
+          let mut Aty = [< DVec $num >]::ZERO;
+          let mut rows = Vec::new();
+
+          for _ in 0..20 {
+            let a: Vec<_> = (0..$num).map(|_| rng.f64() - 0.5).collect();
+            let a = [< DVec $num >]::from_slice(&a);
+            let y = rng.f64() - 0.5;
+            lsq.add_row(a, y);
+            rows.push((a, y));
+
+            AtA += [< DMat $num >]::from_cols_array_2d(&a.to_array().map(|x| (x * a).to_array()));
+            Aty += y * a;
+          }
+
+          // The solution satisfies the normal equations
+          let x = lsq.solve().unwrap();
+          assert!((AtA * x).abs_diff_eq(Aty, 1e-9));
+
+          let residual: f64 = rows.iter().map(|(a, y)| (a.dot(x) - y).powi(2)).sum();
+          assert_abs_diff_eq!(lsq.residual_norm(), residual.sqrt(), epsilon = 1e-9);
+
+          let variance = residual / (20 - $num) as f64;
+          let C = lsq.covariance().unwrap();
+          assert!(C.abs_diff_eq(variance * AtA.inverse(), 1e-9));
+        }
+      }
+    }
+  };
+}
+
+test_least_squares_rand!(2);
+test_least_squares_rand!(3);
+test_least_squares_rand!(4);
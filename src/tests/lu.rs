@@ -0,0 +1,115 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec2, dvec3, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+use paste::paste;
+
+/// Asserts that the LU decomposition of A is valid and solves A x = b
+macro_rules! assert_lu {
+  ($num:expr, $A:expr, $b:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let lu = A.lu();
+      assert!((lu.L * lu.U).abs_diff_eq(lu.permutation() * A, EPS));
+
+      // L is unit lower triangular with entries bounded by 1, and U is upper triangular
+      let L = lu.L.to_cols_array_2d();
+      let U = lu.U.to_cols_array_2d();
+      for j in 0..$num {
+        assert_eq!(L[j][j], 1.0);
+        for i in 0..j {
+          assert_eq!(L[j][i], 0.0);
+        }
+        for i in j + 1..$num {
+          assert!(L[j][i].abs() <= 1.0);
+          assert_eq!(U[j][i], 0.0);
+        }
+      }
+
+      assert_abs_diff_eq!(lu.determinant(), A.determinant(), epsilon = EPS);
+
+      if !lu.singular {
+        let b: [< DVec $num >] = $b;
+        let x = lu.solve(b).unwrap();
+        assert!((A * x).abs_diff_eq(b, 1e-9 * x.length().max(1.0)));
+
+        // Each column of B is solved independently
+        let B = [< DMat $num >]::IDENTITY;
+        let X = lu.solve_mat(B).unwrap().to_cols_array_2d();
+        let expected = B.to_cols_array_2d().map(|b| lu.solve(b.into()).unwrap().to_array());
+        assert_eq!(X, expected);
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_lu() {
+  let A = DMat3::from_cols_array(&[2.0, 4.0, -2.0, 1.0, -6.0, 7.0, 1.0, 0.0, 2.0]);
+  assert_lu!(3, A, dvec3(5.0, -2.0, 9.0));
+  let x = A.lu().solve(dvec3(5.0, -2.0, 9.0)).unwrap();
+  assert!(x.abs_diff_eq(A.inverse() * dvec3(5.0, -2.0, 9.0), EPS));
+
+  // A zero leading entry requires a row swap
+  let A = DMat2::from_cols_array(&[0.0, 1.0, 1.0, 0.0]);
+  assert_lu!(2, A, dvec2(1.0, 2.0));
+  assert_eq!(A.lu().perm, [1, 0]);
+  assert_eq!(A.lu().solve(dvec2(1.0, 2.0)), Some(dvec2(2.0, 1.0)));
+
+  // Singular matrices are flagged and have no solutions
+  let v = dvec3(1.0, -2.0, 0.5);
+  for A in [DMat3::ZERO, DMat3::from_cols(v, 2.0 * v, -v)] {
+    assert_lu!(3, A, DVec3::ONE);
+    assert!(A.lu().singular);
+    assert_eq!(A.lu().solve(DVec3::ONE), None);
+    assert_eq!(A.lu().solve_mat(DMat3::IDENTITY), None);
+  }
+  let A = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+  ]);
+  assert_lu!(4, A, DVec4::ONE);
+  assert!(A.lu().singular);
+
+  // Non-finite matrices are flagged rather than solved into NaN
+  for A in [DMat3::IDENTITY * f64::NAN, DMat3::IDENTITY * f64::INFINITY] {
+    assert!(A.lu().singular);
+    assert_eq!(A.lu().solve(DVec3::ONE), None);
+    assert_eq!(A.lu().solve_mat(DMat3::IDENTITY), None);
+  }
+  let mut A = DMat2::IDENTITY;
+  A.y_axis.x = f64::NAN;
+  assert_eq!(A.lu().solve(DVec2::ONE), None);
+
+  // Nearly singular matrices, where inverse() returns large garbage, are flagged too
+  let A = DMat2::from_cols_array(&[1.0, 1.0, 1.0, 1.0 + 1e-17]);
+  assert!(A.lu().singular);
+  assert_lu!(2, A, DVec2::ONE);
+
+  // A badly scaled but regular matrix is not flagged
+  let A = DMat2::from_cols_array(&[1e-200, 0.0, 0.0, 1e-200]);
+  assert!(!A.lu().singular);
+  assert_eq!(A.lu().solve(dvec2(1e-200, 2e-200)), Some(dvec2(1.0, 2.0)));
+}
+
+macro_rules! test_lu_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_lu $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let b: Vec<_> = (0..$num).map(|_| rng.f64() - 0.5).collect();
+          assert_lu!($num, [< DMat $num >]::from_cols_slice(&v), [< DVec $num >]::from_slice(&b));
+        }
+      }
+    }
+  };
+}
+
+test_lu_rand!(2);
+test_lu_rand!(3);
+test_lu_rand!(4);
@@ -0,0 +1,108 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec3, DMat2, DMat3, DMat4};
+use paste::paste;
+
+#[test]
+fn test_norms() {
+  let A = DMat2::from_cols_array(&[1.0, -3.0, 2.0, 4.0]);
+  assert_eq!(A.trace(), 5.0);
+  assert_eq!(A.norm1(), 6.0);
+  assert_eq!(A.norm_inf(), 7.0);
+  assert_eq!(A.norm_max(), 4.0);
+  assert_abs_diff_eq!(A.norm_frobenius(), 30f64.sqrt(), epsilon = EPS);
+  // σ² are the eigenvalues of AᵀA = [10 -10; -10 20]
+  assert_abs_diff_eq!(A.norm2(), (15.0 + 125f64.sqrt()).sqrt(), epsilon = EPS);
+  assert_abs_diff_eq!(A.cond1(), 6.0 * 0.7, epsilon = EPS);
+  assert_abs_diff_eq!(A.spectral_radius(), 10f64.sqrt(), epsilon = EPS);
+
+  let A = DMat3::from_diagonal(dvec3(2.0, -8.0, 0.5));
+  assert_eq!(A.norm2(), 8.0);
+  assert_eq!(A.cond1(), 16.0);
+  assert_eq!(A.cond2(), 16.0);
+  assert_eq!(A.spectral_radius(), 8.0);
+
+  // The norms of orthogonal matrices
+  let Q = DMat4::from_quat(glam::DQuat::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0));
+  assert_abs_diff_eq!(Q.norm2(), 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(Q.cond2(), 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(Q.norm_frobenius(), 2.0, epsilon = EPS);
+  assert_abs_diff_eq!(Q.spectral_radius(), 1.0, epsilon = EPS);
+
+  // Singular matrices are infinitely badly conditioned
+  let v = dvec3(1.0, -2.0, 0.5);
+  for A in [DMat3::ZERO, DMat3::from_cols(v, 2.0 * v, -v)] {
+    assert_eq!(A.cond1(), f64::INFINITY);
+    assert_eq!(A.cond2(), f64::INFINITY);
+  }
+  assert_eq!(DMat3::ZERO.norm_frobenius(), 0.0);
+
+  // Nearly singular matrices have a large but finite condition number in both norms
+  let A = DMat3::from_diagonal(dvec3(1.0, 1e-17, 1.0));
+  assert_eq!(A.cond1(), 1e17);
+  assert_eq!(A.cond2(), 1e17);
+
+  assert!((DMat3::IDENTITY * f64::NAN).spectral_radius().is_nan());
+  assert!((DMat4::IDENTITY * f64::INFINITY).spectral_radius().is_nan());
+
+  // A NaN entry makes every norm NaN, rather than being skipped by the maximum
+  let mut A = DMat3::from_diagonal(dvec3(2.0, -8.0, 0.5));
+  A.y_axis.x = f64::NAN;
+  assert!(A.norm1().is_nan());
+  assert!(A.norm_inf().is_nan());
+  assert!(A.norm_max().is_nan());
+  assert!(A.norm_frobenius().is_nan());
+  assert!(A.cond1().is_nan());
+  assert!((DMat2::IDENTITY * f64::NAN).cond1().is_nan());
+  assert!((DMat4::IDENTITY * f64::INFINITY).cond1().is_nan());
+
+  // The Frobenius norm neither overflows nor underflows
+  assert_abs_diff_eq!(
+    (DMat2::IDENTITY * 1e300).norm_frobenius(),
+    2f64.sqrt() * 1e300,
+    epsilon = 1e285
+  );
+  assert_abs_diff_eq!(
+    (DMat2::IDENTITY * 1e-300).norm_frobenius(),
+    2f64.sqrt() * 1e-300,
+    epsilon = 1e-315
+  );
+}
+
+macro_rules! test_norms_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_norms $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS / 10 {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          let B = nalgebra::[< Matrix $num >]::from_column_slice(&v);
+
+          assert_abs_diff_eq!(A.norm_frobenius(), B.norm(), epsilon = EPS);
+          assert_abs_diff_eq!(A.norm1(), A.transpose().norm_inf(), epsilon = EPS);
+          assert_abs_diff_eq!(A.norm2(), B.singular_values().max(), epsilon = EPS);
+
+          // ‖A‖₂ is bounded by the other norms, and bounds the spectral radius
+          let norm2 = A.norm2();
+          assert!(norm2 <= A.norm_frobenius() + EPS);
+          assert!(norm2 <= (A.norm1() * A.norm_inf()).sqrt() + EPS);
+          assert!(A.spectral_radius() <= norm2 + EPS);
+
+          let cond1 = A.norm1() * A.inverse().norm1();
+          assert_abs_diff_eq!(A.cond1() / cond1, 1.0, epsilon = 1e-8);
+          assert!(A.cond2() >= 1.0);
+        }
+      }
+    }
+  };
+}
+
+test_norms_rand!(2);
+test_norms_rand!(3);
+test_norms_rand!(4);
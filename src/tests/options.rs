@@ -0,0 +1,118 @@
+use crate::tests::*;
+use crate::*;
+
+use fastrand::Rng;
+use glam::{DMat2, DMat3, DMat4, Mat4};
+
+#[test]
+fn test_eigvals_with() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ]);
+  let options = EigenOptions::default();
+  let (eigvals, diagnostics) = A.eigvals_with(&options).unwrap();
+  assert_eq!(eigvals, A.eigvals());
+  assert!(diagnostics.iterations > 0);
+  assert_eq!(diagnostics.subdiagonal().len(), 3);
+
+  // One real eigenvalue deflates on each side of the complex pair
+  let [a, b, c] = diagnostics.subdiagonal().try_into().unwrap();
+  assert!(a.min(c) <= 1e-14 * A.norm_max());
+  assert!(b > 0.1);
+
+  // The 2x2 eigenvalues are found directly
+  let A = DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]);
+  let (eigvals, diagnostics) = A.eigvals_with(&options).unwrap();
+  assert_eq!(eigvals, A.eigvals());
+  assert_eq!(diagnostics.iterations, 0);
+  assert_eq!(diagnostics.subdiagonal(), [2.0]);
+
+  // The options have no effect on the closed form
+  let ignored = EigenOptions::default()
+    .with_max_iterations(0)
+    .with_tolerance(0.0);
+  assert_eq!(A.eigvals_with(&ignored).unwrap(), (eigvals, diagnostics));
+
+  let A = Mat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ]);
+  let (eigvals, _) = A.eigvals_with(&options).unwrap();
+  assert_eq!(eigvals, A.eigvals());
+}
+
+#[test]
+fn test_max_iterations() {
+  // Already triangular matrices deflate without iterating
+  let A = DMat3::from_cols_array(&[1.0, 0.0, 0.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0]);
+  let options = EigenOptions::default().with_max_iterations(0);
+  let (eigvals, diagnostics) = A.eigvals_with(&options).unwrap();
+  assert_eq!(eigvals, [1.0, 3.0, 6.0].map(Complex64::from));
+  assert_eq!(diagnostics.iterations, 0);
+  assert_eq!(diagnostics.subdiagonal(), [0.0, 0.0]);
+
+  let A = DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+  assert_eq!(
+    A.eigvals_with(&options),
+    Err(LinalgError::NoConvergence { iterations: 0 })
+  );
+  assert_eq!(
+    A.eigvals_with(&EigenOptions::default().with_max_iterations(1)),
+    Err(LinalgError::NoConvergence { iterations: 1 })
+  );
+
+  let A = DMat3::IDENTITY * f64::NAN;
+  assert_eq!(
+    A.eigvals_with(&EigenOptions::default()),
+    Err(LinalgError::NonFiniteInput)
+  );
+}
+
+#[test]
+fn test_exceptional_shifts() {
+  // The QR iteration with standard shifts leaves a cyclic permutation unchanged, so that only an exceptional
+  // shift can make progress
+  let A = DMat3::from_cols_array(&[0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+
+  let options = EigenOptions::default();
+  let (eigvals, diagnostics) = A.eigvals_with(&options).unwrap();
+  assert_valid(&eigvals, 0.0, 0.0, 1.0, EPS);
+  assert!(diagnostics.iterations > 10);
+
+  assert_eq!(
+    A.eigvals_with(&options.with_exceptional_shifts(false)),
+    Err(LinalgError::NoConvergence { iterations: 100 })
+  );
+
+  // The same holds for the 4x4 cyclic permutation, whose eigenvalues are ±1 and ±i
+  let A = DMat4::from_cols_array(&[
+    0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0,
+  ]);
+  let (eigvals, _) = A.eigvals_with(&options).unwrap();
+  assert_valid(&eigvals, 0.0, 0.0, -1.0, EPS);
+  assert!(A
+    .eigvals_with(&options.with_exceptional_shifts(false))
+    .is_err());
+}
+
+#[test]
+fn test_tolerance() {
+  let mut rng = Rng::with_seed(SEED);
+  let loose = EigenOptions::default().with_tolerance(1e-6);
+  let (mut default_iterations, mut loose_iterations) = (0, 0);
+
+  for _ in 0..N_TESTS / 10 {
+    let v: Vec<_> = (0..16).map(|_| rng.f64() - 0.5).collect();
+    let A = DMat4::from_cols_slice(&v);
+
+    let (_, diagnostics) = A.eigvals_with(&EigenOptions::default()).unwrap();
+    default_iterations += diagnostics.iterations;
+
+    // Deflated entries fall below the tolerance, at the cost of accuracy in the eigenvalues
+    let (eigvals, diagnostics) = A.eigvals_with(&loose).unwrap();
+    loose_iterations += diagnostics.iterations;
+    assert!(diagnostics.subdiagonal().iter().any(|&x| x <= 1e-6));
+    assert_valid(&eigvals, A.trace(), (A * A).trace(), A.determinant(), 1e-5);
+  }
+
+  assert!(loose_iterations < default_iterations);
+}
@@ -0,0 +1,125 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec3, DMat3, DMat4, Mat3};
+use paste::paste;
+
+#[test]
+fn test_eigvals_ordered() {
+  let A = DMat3::from_diagonal(dvec3(-3.0, 1.0, 2.0));
+  assert_eq!(A.eigvals_symmetric(), [-3.0, 1.0, 2.0]);
+  assert_eq!(
+    A.eigvals_symmetric_ordered(EigenOrder::Descending),
+    [2.0, 1.0, -3.0]
+  );
+  assert_eq!(
+    A.eigvals_symmetric_ordered(EigenOrder::LargestMagnitude),
+    [-3.0, 2.0, 1.0]
+  );
+
+  // Ties in magnitude fall back to ascending order
+  let A = DMat4::from_diagonal(glam::dvec4(2.0, -1.0, 1.0, -2.0));
+  assert_eq!(
+    A.eigvals_symmetric_ordered(EigenOrder::LargestMagnitude),
+    [-2.0, 2.0, -1.0, 1.0]
+  );
+
+  // The general methods order by real part, with each conjugate pair adjacent
+  let A = DMat4::from_cols_array(&[
+    0.0, 1.0, 0.0, 0.0, -4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, -1.0,
+  ]);
+  let [a, b, c, d] = A.eigvals();
+  assert_complex_eq(a, Complex64::new(-1.0, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(0.0, -2.0), EPS);
+  assert_complex_eq(c, Complex64::new(0.0, 2.0), EPS);
+  assert_complex_eq(d, Complex64::new(3.0, 0.0), EPS);
+
+  let [a, b, c, d] = A.eigvals_ordered(EigenOrder::Descending);
+  assert_complex_eq(a, Complex64::new(3.0, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(0.0, 2.0), EPS);
+  assert_complex_eq(c, Complex64::new(0.0, -2.0), EPS);
+  assert_complex_eq(d, Complex64::new(-1.0, 0.0), EPS);
+
+  let [a, b, c, d] = A.eigvals_ordered(EigenOrder::LargestMagnitude);
+  assert_complex_eq(a, Complex64::new(3.0, 0.0), EPS);
+  assert_complex_eq(b, Complex64::new(0.0, -2.0), EPS);
+  assert_complex_eq(c, Complex64::new(0.0, 2.0), EPS);
+  assert_complex_eq(d, Complex64::new(-1.0, 0.0), EPS);
+
+  // The f32 variants follow the same order
+  let A = Mat3::from_diagonal(glam::vec3(1.0, -5.0, 3.0));
+  assert_eq!(A.eigvals_symmetric(), [-5.0, 1.0, 3.0]);
+  let (eigvals, V) = A.eig_symmetric_ordered(EigenOrder::LargestMagnitude);
+  assert_eq!(eigvals, [-5.0, 3.0, 1.0]);
+  assert_eq!(V.x_axis.abs(), glam::Vec3::Y);
+}
+
+macro_rules! test_order_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_eig $num _ordered_rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS / 10 {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          let S = A + A.transpose();
+
+          // The default order is ascending for all sizes
+          let eigvals = A.eigvals();
+          assert_eq!(A.eigvals_ordered(EigenOrder::Ascending), eigvals);
+          assert!(eigvals.windows(2).all(|w| w[0].re <= w[1].re));
+          let eigvals = S.eigvals_symmetric();
+          assert!(eigvals.windows(2).all(|w| w[0] <= w[1]));
+
+          for order in [EigenOrder::Ascending, EigenOrder::Descending, EigenOrder::LargestMagnitude] {
+            let (eigvals, re, im) = A.eig_ordered(order);
+            for (a, b) in eigvals.into_iter().zip(A.eigvals_ordered(order)) {
+              assert_complex_eq(a, b, EPS);
+            }
+            assert_eigvecs(
+              A.to_cols_array_2d(),
+              &eigvals,
+              re.to_cols_array_2d(),
+              im.to_cols_array_2d(),
+              EPS,
+            );
+
+            let (eigvals, V) = S.eig_symmetric_ordered(order);
+            for (a, b) in eigvals.into_iter().zip(S.eigvals_symmetric_ordered(order)) {
+              assert_abs_diff_eq!(a, b, epsilon = EPS);
+            }
+            let D = [< DMat $num >]::from_diagonal(glam::[< DVec $num >]::from_array(eigvals));
+            assert!((V * D * V.transpose()).abs_diff_eq(S, EPS));
+          }
+
+          let eigvals = S.eigvals_symmetric_ordered(EigenOrder::LargestMagnitude);
+          assert!(eigvals.windows(2).all(|w| w[0].abs() >= w[1].abs()));
+        }
+      }
+    }
+  };
+}
+
+test_order_rand!(2);
+test_order_rand!(3);
+test_order_rand!(4);
+
+#[test]
+fn test_eig3_symmetric_ordered() {
+  // The eigenvectors follow their eigenvalues, for both the isolated largest and smallest eigenvalue
+  for A in [
+    DMat3::from_diagonal(dvec3(1.0, 2.0, 10.0)),
+    DMat3::from_diagonal(dvec3(10.0, 2.0, 1.0)),
+    DMat3::from_diagonal(dvec3(-10.0, 2.0, 3.0)),
+  ] {
+    let (eigvals, V) = A.eig_symmetric();
+    for (lambda, v) in eigvals.into_iter().zip([V.x_axis, V.y_axis, V.z_axis]) {
+      assert!((A * v).abs_diff_eq(lambda * v, EPS));
+    }
+    assert!(eigvals.windows(2).all(|w| w[0] <= w[1]));
+  }
+}
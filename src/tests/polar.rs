@@ -0,0 +1,78 @@
+use crate::tests::*;
+use crate::*;
+
+use fastrand::Rng;
+use glam::{dvec3, DMat2, DMat3, EulerRot};
+use paste::paste;
+
+/// Asserts that (R, S) is a valid polar decomposition of A, with R a proper rotation if $proper is set
+macro_rules! assert_polar {
+  ($num:expr, $A:expr, $proper:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (R, S) = if $proper { A.polar_rotation() } else { A.polar() };
+      assert!((R * S).abs_diff_eq(A, EPS));
+      assert!((R.transpose() * R).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+      assert_eq!(S, S.transpose());
+
+      if $proper {
+        assert!((R.determinant() - 1.0).abs() < EPS);
+      } else {
+        // The stretch is positive semidefinite
+        assert!(S.eigvals_symmetric().iter().all(|&lambda| lambda >= -EPS));
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_polar() {
+  for proper in [false, true] {
+    assert_polar!(2, DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]), proper);
+    assert_polar!(2, DMat2::ZERO, proper);
+    assert_polar!(2, DMat2::from_diagonal((-1.0, 1.0).into()), proper);
+    assert_polar!(
+      3,
+      DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]),
+      proper
+    );
+    assert_polar!(3, DMat3::ZERO, proper);
+    assert_polar!(3, DMat3::from_diagonal(dvec3(2.0, -3.0, 1.0)), proper);
+    let v = dvec3(1.0, -2.0, 0.5);
+    assert_polar!(3, DMat3::from_cols(v, 2.0 * v, -v), proper);
+  }
+
+  // A rotation times a stretch is recovered exactly
+  let Q = DMat3::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.0);
+  let S = DMat3::from_cols_array(&[2.0, 0.5, 0.0, 0.5, 3.0, -1.0, 0.0, -1.0, 4.0]);
+  let (R, P) = (Q * S).polar();
+  assert!(R.abs_diff_eq(Q, EPS));
+  assert!(P.abs_diff_eq(S, EPS));
+
+  // The rotation closest to a reflection keeps the flip along the direction of least stretch in S
+  let A = DMat3::from_diagonal(dvec3(3.0, 2.0, -1.0));
+  let (R, S) = A.polar_rotation();
+  assert!(R.abs_diff_eq(DMat3::IDENTITY, EPS));
+  assert!(S.abs_diff_eq(A, EPS));
+}
+
+macro_rules! test_polar_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_polar $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          assert_polar!($num, A, false);
+          assert_polar!($num, A, true);
+        }
+      }
+    }
+  };
+}
+
+test_polar_rand!(2);
+test_polar_rand!(3);
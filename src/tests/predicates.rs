@@ -0,0 +1,102 @@
+use crate::*;
+
+use glam::{dvec3, DMat2, DMat3, DMat4, DQuat, EulerRot};
+
+#[test]
+fn test_predicates() {
+  let A = DMat3::from_cols_array(&[4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]);
+  assert!(A.is_symmetric(0.0));
+  assert!(A.is_positive_definite(0.0));
+  assert!(!A.is_skew_symmetric(0.0));
+  assert!(!A.is_diagonal(0.0));
+  assert!(!A.is_hessenberg(0.0));
+
+  // The tolerance is absolute
+  let mut B = A;
+  B.x_axis.y += 1e-9;
+  assert!(!B.is_symmetric(0.0));
+  assert!(B.is_symmetric(1e-8));
+  assert!(!B.is_positive_definite(0.0));
+  assert!(B.is_positive_definite(1e-8));
+
+  let A = DMat3::from_cols(
+    dvec3(0.0, 2.0, -1.0),
+    dvec3(-2.0, 0.0, 3.0),
+    dvec3(1.0, -3.0, 0.0),
+  );
+  assert!(A.is_skew_symmetric(0.0));
+  assert!(!A.is_symmetric(0.0));
+  assert!(!(A + DMat3::IDENTITY).is_skew_symmetric(0.0));
+
+  // Rotations and reflections
+  let Q = DMat4::from_quat(DQuat::from_euler(EulerRot::XYZ, 0.3, -1.2, 2.0));
+  assert!(Q.is_orthogonal(1e-12));
+  assert!(Q.is_rotation(1e-12));
+  let R = Q * DMat4::from_diagonal((1.0, 1.0, -1.0, 1.0).into());
+  assert!(R.is_orthogonal(1e-12));
+  assert!(!R.is_rotation(1e-12));
+  assert!(!(2.0 * Q).is_orthogonal(1e-12));
+
+  // Definiteness
+  assert!(!DMat2::ZERO.is_positive_definite(0.0));
+  assert!(!DMat2::from_cols_array(&[1.0, 2.0, 2.0, 1.0]).is_positive_definite(0.0));
+  assert!(!DMat2::from_cols_array(&[1.0, 0.0, 1.0, 1.0]).is_positive_definite(0.0));
+  assert!(DMat2::from_diagonal((1e-3, 1.0).into()).is_positive_definite(0.0));
+  assert!(!DMat2::from_diagonal((1e-3, 1.0).into()).is_positive_definite(1e-2));
+  assert!(!(DMat2::IDENTITY * f64::NAN).is_positive_definite(0.0));
+
+  // Triangular, hessenberg and diagonal structure
+  let U = DMat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0, 2.0, 3.0, 0.0, 0.0, 4.0, 5.0, 6.0, 0.0, 7.0, 8.0, 9.0, 10.0,
+  ]);
+  assert!(U.is_upper_triangular(0.0));
+  assert!(U.is_hessenberg(0.0));
+  assert!(!U.is_diagonal(0.0));
+  assert!(!U.transpose().is_upper_triangular(0.0));
+
+  let H = DMat4::from_cols_array(&[
+    1.0, 2.0, 0.0, 0.0, 2.0, 3.0, 4.0, 0.0, 4.0, 5.0, 6.0, 7.0, 7.0, 8.0, 9.0, 10.0,
+  ]);
+  assert!(H.is_hessenberg(0.0));
+  assert!(!H.is_upper_triangular(0.0));
+  let mut H = H;
+  H.x_axis.z = 1e-10;
+  assert!(!H.is_hessenberg(0.0));
+  assert!(H.is_hessenberg(1e-9));
+
+  let (_, H) = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+  ])
+  .hessenberg();
+  assert!(H.is_hessenberg(1e-12));
+
+  assert!(DMat3::from_diagonal(dvec3(1.0, -2.0, 3.0)).is_diagonal(0.0));
+  assert!(DMat3::ZERO.is_diagonal(0.0));
+  assert!(DMat3::ZERO.is_upper_triangular(0.0));
+}
+
+#[test]
+#[cfg(feature = "glam-assert")]
+#[should_panic(expected = "the matrix is not symmetric")]
+fn test_glam_assert_symmetric() {
+  DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]).eigvals_symmetric();
+}
+
+#[test]
+#[cfg(feature = "glam-assert")]
+fn test_glam_assert_non_finite() {
+  // Non-finite input fails the symmetry check, but should give NaN rather than panicking
+  let eigvals = (DMat2::IDENTITY * f64::NAN).eigvals_symmetric();
+  assert!(eigvals.iter().all(|x| x.is_nan()));
+  let (eigvals, V) = (DMat4::IDENTITY * f64::INFINITY).eig_symmetric();
+  assert!(eigvals.iter().all(|x| x.is_nan()) && V.is_nan());
+}
+
+#[test]
+#[cfg(feature = "glam-assert")]
+fn test_glam_assert_positive_definite() {
+  // The asymmetry is within the tolerance, but far beyond that of the symmetry assertion
+  let A = DMat2::from_cols_array(&[2.0, 0.1, 0.0, 2.0]);
+  assert!(A.is_positive_definite(0.5));
+  assert!(!A.is_positive_definite(0.05));
+}
@@ -0,0 +1,108 @@
+use crate::tests::*;
+use crate::*;
+
+use fastrand::Rng;
+use glam::{dvec3, DMat2, DMat3, DMat4, DVec3};
+use paste::paste;
+
+/// Asserts that (Q, R) is a valid QR decomposition of A
+macro_rules! assert_qr {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (Q, R) = A.qr();
+      assert!((Q * R).abs_diff_eq(A, EPS));
+      assert!((Q.transpose() * Q).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+
+      let R = R.to_cols_array_2d();
+      for j in 0..$num {
+        for i in j + 1..$num {
+          assert_eq!(R[j][i], 0.0);
+        }
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_qr() {
+  assert_qr!(2, DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
+  assert_qr!(2, DMat2::from_cols_array(&[0.0, 1.0, 1.0, 0.0]));
+  assert_qr!(
+    3,
+    DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0])
+  );
+  assert_qr!(
+    4,
+    DMat4::from_cols_array(&[
+      1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    ])
+  );
+
+  // Zero columns and zero leading entries
+  assert_qr!(2, DMat2::ZERO);
+  assert_qr!(3, DMat3::ZERO);
+  assert_qr!(4, DMat4::ZERO);
+  assert_qr!(
+    3,
+    DMat3::from_cols(DVec3::ZERO, dvec3(1.0, 2.0, 3.0), DVec3::ZERO)
+  );
+  assert_qr!(
+    3,
+    DMat3::from_cols(dvec3(0.0, 0.0, 1.0), dvec3(0.0, 1.0, 0.0), DVec3::X)
+  );
+
+  // Rank deficient matrices
+  let v = dvec3(1.0, -2.0, 0.5);
+  assert_qr!(3, DMat3::from_cols(v, 2.0 * v, -v));
+  assert_qr!(4, DMat4::from_cols_array(&[1.0; 16]));
+
+  // Already triangular matrices should be left as is, up to signs
+  let A = DMat3::from_cols_array(&[2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 4.0, 5.0, 6.0]);
+  let (Q, R) = A.qr();
+  assert!(Q.abs_diff_eq(DMat3::IDENTITY, EPS));
+  assert!(R.abs_diff_eq(A, EPS));
+}
+
+#[test]
+fn test_qr_scaled() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.5, 16.0,
+  ]);
+
+  // The reconstruction should be accurate relative to A even where the squared entries overflow or underflow
+  for scale in [1e200, 1e-200] {
+    let A = A * scale;
+    let (Q, R) = A.qr();
+    assert!((Q * R * scale.recip()).abs_diff_eq(A * scale.recip(), EPS));
+    assert!((Q.transpose() * Q).abs_diff_eq(DMat4::IDENTITY, EPS));
+
+    let A = DMat3::from_mat4(A);
+    let (Q, R) = A.qr();
+    assert!((Q * R * scale.recip()).abs_diff_eq(A * scale.recip(), EPS));
+
+    let A = DMat2::from_mat3(A);
+    let (Q, R) = A.qr();
+    assert!((Q * R * scale.recip()).abs_diff_eq(A * scale.recip(), EPS));
+  }
+}
+
+macro_rules! test_qr_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_qr $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          assert_qr!($num, [< DMat $num >]::from_cols_slice(&v));
+        }
+      }
+    }
+  };
+}
+
+test_qr_rand!(2);
+test_qr_rand!(3);
+test_qr_rand!(4);
@@ -0,0 +1,129 @@
+use crate::tests::*;
+use crate::*;
+
+use fastrand::Rng;
+use glam::{DMat2, DMat3, DMat4};
+use paste::paste;
+
+/// Asserts that (Z, T) is a valid real Schur decomposition of A, returning the eigenvalues read off T
+macro_rules! assert_schur {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (Z, T) = A.schur();
+      assert!((Z * T * Z.transpose()).abs_diff_eq(A, EPS));
+      assert!((Z.transpose() * Z).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+      schur_eigvals(T.to_cols_array_2d())
+    }}
+  };
+}
+
+/// Checks that T is in standardized real Schur form, and reads the eigenvalues off its diagonal blocks
+fn schur_eigvals<const N: usize>(T: [[f64; N]; N]) -> Vec<Complex64> {
+  for (j, col) in T.iter().enumerate() {
+    assert!(col.iter().skip(j + 2).all(|&x| x == 0.0));
+  }
+
+  let mut eigvals = Vec::new();
+  let mut k = 0;
+  while k < N {
+    if k + 1 < N && T[k][k + 1] != 0.0 {
+      // The 2x2 blocks must be standardized, and cannot overlap
+      assert_eq!(T[k][k], T[k + 1][k + 1]);
+      assert!(T[k][k + 1] * T[k + 1][k] < 0.0);
+      assert!(k + 2 >= N || T[k + 1][k + 2] == 0.0);
+
+      let omega = (-T[k][k + 1] * T[k + 1][k]).sqrt();
+      eigvals.push(Complex64::new(T[k][k], omega));
+      eigvals.push(Complex64::new(T[k][k], -omega));
+      k += 2;
+    } else {
+      eigvals.push(Complex64::new(T[k][k], 0.0));
+      k += 1;
+    }
+  }
+  eigvals
+}
+
+#[test]
+fn test_schur() {
+  // A rotation has a complex pair, given by a single standardized block
+  let eigvals = assert_schur!(2, DMat2::from_angle(0.5));
+  assert_complex_eq(eigvals[0], Complex64::new(0.5f64.cos(), 0.5f64.sin()), EPS);
+
+  // Real eigenvalues give an upper triangular T
+  let eigvals = assert_schur!(2, DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
+  assert!(eigvals.iter().all(|x| x.im == 0.0));
+
+  // Real 2x2 blocks with nearly equal diagonals, and blocks needing a swap
+  assert_schur!(2, DMat2::from_cols_array(&[1.0, 1e-10, 1e-10, 1.0]));
+  assert_schur!(2, DMat2::from_cols_array(&[1.0, 1.0, 0.0, 2.0]));
+
+  let eigvals = assert_schur!(
+    3,
+    DMat3::from_cols_array(&[1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0])
+  );
+  assert_valid(&eigvals, 3.0, 3.0, 2.0, EPS);
+
+  let A = DMat4::from_cols_array(&[
+    1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+  ]);
+  let eigvals = assert_schur!(4, A);
+  assert_valid(&eigvals, A.trace(), (A * A).trace(), A.determinant(), EPS);
+
+  // Two complex pairs
+  let A = DMat4::from_cols_array(&[
+    0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 1.0, 0.0, -2.0, 0.0,
+  ]);
+  let eigvals = assert_schur!(4, A);
+  assert_valid(&eigvals, A.trace(), (A * A).trace(), A.determinant(), EPS);
+  assert!(eigvals.iter().all(|x| x.im != 0.0));
+}
+
+#[test]
+fn test_schur_scaled() {
+  let A = DMat4::from_cols_array(&[
+    1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.5,
+  ]);
+
+  // The decomposition should converge and be accurate relative to A at any scale
+  for scale in [1e150, 1e200, 1e-170, 1e-200] {
+    let (Z, T) = (A * scale).try_schur().unwrap();
+    assert!((Z * T * Z.transpose() * scale.recip()).abs_diff_eq(A, EPS));
+    assert!((Z.transpose() * Z).abs_diff_eq(DMat4::IDENTITY, EPS));
+    let eigvals = schur_eigvals((T * scale.recip()).to_cols_array_2d());
+    assert_valid(
+      &eigvals,
+      A.trace(),
+      (A * A).trace(),
+      A.determinant(),
+      1e3 * EPS,
+    );
+
+    let A = DMat3::from_mat4(A);
+    let (Z, T) = (A * scale).try_schur().unwrap();
+    assert!((Z * T * Z.transpose() * scale.recip()).abs_diff_eq(A, EPS));
+  }
+}
+
+macro_rules! test_schur_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_schur $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64()).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          let eigvals = assert_schur!($num, A);
+          assert_valid(&eigvals, A.trace(), (A * A).trace(), A.determinant(), EPS);
+        }
+      }
+    }
+  };
+}
+
+test_schur_rand!(2);
+test_schur_rand!(3);
+test_schur_rand!(4);
@@ -0,0 +1,96 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec4, DMat2, DMat3, DMat4, Mat3};
+use paste::paste;
+
+#[test]
+fn test_spectrum() {
+  let spectrum = DMat2::from_cols_array(&[1.0, -1.0, 1.0, 1.0]).spectrum();
+  assert_eq!(
+    spectrum.as_slice(),
+    [Eigenvalue::ConjugatePair { re: 1.0, im: 1.0 }]
+  );
+  assert!(!spectrum.is_real());
+  assert_eq!(spectrum.real().count(), 0);
+  assert_eq!(
+    spectrum.conjugate_pairs().collect::<Vec<_>>(),
+    [Complex64::new(1.0, 1.0)]
+  );
+  assert_eq!(
+    spectrum.to_complex(),
+    [Complex64::new(1.0, -1.0), Complex64::new(1.0, 1.0)]
+  );
+
+  // Repeated real eigenvalues are not mistaken for a pair
+  let spectrum = DMat2::from_cols_array(&[2.0, 0.0, 1.0, 2.0]).spectrum();
+  assert_eq!(
+    spectrum.as_slice(),
+    [Eigenvalue::Real(2.0), Eigenvalue::Real(2.0)]
+  );
+
+  // A rotation about the z axis by 60 degrees
+  let (sin, cos) = std::f64::consts::FRAC_PI_3.sin_cos();
+  let spectrum = DMat3::from_rotation_z(std::f64::consts::FRAC_PI_3).spectrum();
+  assert_eq!(spectrum.as_slice().len(), 2);
+  assert_eq!(spectrum.real().collect::<Vec<_>>(), [1.0]);
+  let [pair] = spectrum.conjugate_pairs().collect::<Vec<_>>()[..] else {
+    panic!("expected a single conjugate pair");
+  };
+  assert_complex_eq(pair, Complex64::new(cos, sin), EPS);
+
+  // Two decoupled rotation blocks
+  let A = DMat4::from_cols_array(&[
+    0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, -2.0, 0.0,
+  ]);
+  let spectrum = A.spectrum();
+  assert_eq!(spectrum.real().count(), 0);
+  let pairs: Vec<_> = spectrum.conjugate_pairs().map(|z| z.im).collect();
+  assert_abs_diff_eq!(pairs[0], 1.0, epsilon = EPS);
+  assert_abs_diff_eq!(pairs[1], 2.0, epsilon = EPS);
+
+  let spectrum = DMat4::from_diagonal(dvec4(4.0, 3.0, 2.0, 1.0)).spectrum();
+  assert!(spectrum.is_real());
+  assert_eq!(spectrum.real().collect::<Vec<_>>(), [1.0, 2.0, 3.0, 4.0]);
+
+  let spectrum = Mat3::from_rotation_x(1.0).spectrum();
+  assert_eq!(spectrum.real().collect::<Vec<f32>>(), [1.0]);
+  // The pair has real part cos(1) < 1, so it comes first
+  assert!(matches!(
+    spectrum.as_slice(),
+    [Eigenvalue::ConjugatePair { .. }, Eigenvalue::Real(_)]
+  ));
+
+  assert_eq!(
+    DMat3::from_diagonal(glam::DVec3::splat(f64::NAN)).try_spectrum(),
+    Err(LinalgError::NonFiniteInput)
+  );
+}
+
+macro_rules! test_spectrum_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_spectrum $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          let A = [< DMat $num >]::from_cols_slice(&v);
+          let spectrum = A.spectrum();
+
+          let count: usize = spectrum.iter().map(Eigenvalue::multiplicity).sum();
+          assert_eq!(count, $num);
+          assert!(spectrum.conjugate_pairs().all(|z| z.im > 0.0));
+          assert_eq!(spectrum.to_complex(), A.eigvals());
+        }
+      }
+    }
+  };
+}
+
+test_spectrum_rand!(2);
+test_spectrum_rand!(3);
+test_spectrum_rand!(4);
@@ -0,0 +1,176 @@
+use crate::tests::*;
+use crate::*;
+
+use approx::assert_abs_diff_eq;
+use fastrand::Rng;
+use glam::{dvec3, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+use paste::paste;
+
+/// Asserts that (U, σ, Vᵀ) is a valid singular value decomposition of A
+macro_rules! assert_svd {
+  ($num:expr, $A:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (U, sigma, Vt) = A.svd();
+      let S = [< DMat $num >]::from_diagonal(sigma.into());
+      assert!((U * S * Vt).abs_diff_eq(A, EPS));
+      assert!((U.transpose() * U).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+      assert!((Vt * Vt.transpose()).abs_diff_eq([< DMat $num >]::IDENTITY, EPS));
+
+      // The singular values are non-negative and in descending order
+      assert!(sigma.iter().all(|&s| s >= 0.0));
+      assert!(sigma.windows(2).all(|w| w[0] >= w[1]));
+
+      let values = A.singular_values();
+      for (a, b) in values.iter().zip(&sigma) {
+        assert_abs_diff_eq!(a, b, epsilon = EPS);
+      }
+    }}
+  };
+}
+
+#[test]
+fn test_svd() {
+  assert_svd!(2, DMat2::from_cols_array(&[1.0, 2.0, 3.0, 4.0]));
+  assert_svd!(
+    3,
+    DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0])
+  );
+  assert_svd!(
+    4,
+    DMat4::from_cols_array(&[
+      4.0, 1.0, -2.0, 0.5, 1.0, 3.0, 0.0, -1.0, -2.0, 0.0, 5.0, 2.0, 0.5, -1.0, 2.0, 6.0,
+    ])
+  );
+
+  // Zero and rank deficient matrices
+  assert_svd!(2, DMat2::ZERO);
+  assert_svd!(3, DMat3::ZERO);
+  assert_svd!(4, DMat4::ZERO);
+  assert_svd!(2, DMat2::from_cols_array(&[1.0, 2.0, 2.0, 4.0]));
+  let v = dvec3(1.0, -2.0, 0.5);
+  assert_svd!(3, DMat3::from_cols(v, 2.0 * v, -v));
+  assert_svd!(
+    4,
+    DMat4::from_cols_array(&[
+      1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    ])
+  );
+  assert_svd!(4, DMat4::from_cols_array(&[1.0; 16]));
+
+  // Reflections and negative diagonals
+  assert_svd!(2, DMat2::from_diagonal((-3.0, 2.0).into()));
+  assert_svd!(3, DMat3::from_diagonal(dvec3(1.0, -5.0, 2.0)));
+
+  // Rotations have unit singular values
+  let values = DMat3::from_euler(glam::EulerRot::XYZ, 0.3, -1.2, 2.0).singular_values();
+  for s in values {
+    assert_abs_diff_eq!(s, 1.0, epsilon = EPS);
+  }
+
+  // Known values, σ = 3√5 and √5
+  let [s1, s2] = DMat2::from_cols_array(&[3.0, 4.0, 0.0, 5.0]).singular_values();
+  assert_abs_diff_eq!(s1, 3.0 * 5f64.sqrt(), epsilon = EPS);
+  assert_abs_diff_eq!(s2, 5f64.sqrt(), epsilon = EPS);
+}
+
+#[test]
+fn test_svd_scaled() {
+  let A = DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+  let B = DMat4::from_cols_array(&[
+    4.0, 1.0, -2.0, 0.5, 1.0, 3.0, 0.0, -1.0, -2.0, 0.0, 5.0, 2.0, 0.5, -1.0, 2.0, 6.0,
+  ]);
+  let (expected_A, expected_B) = (A.singular_values(), B.singular_values());
+
+  for scale in [1e-300, 1e-160, 1e100, 1e155, 1e300] {
+    let (U, sigma, Vt) = (scale * A).svd();
+    assert!((U.transpose() * U).abs_diff_eq(DMat3::IDENTITY, EPS));
+    assert!((Vt * Vt.transpose()).abs_diff_eq(DMat3::IDENTITY, EPS));
+    let S = DMat3::from_diagonal(DVec3::from(sigma) / scale);
+    assert!((U * S * Vt).abs_diff_eq(A, 10.0 * EPS));
+    for (s, expected) in (scale * A).singular_values().into_iter().zip(expected_A) {
+      assert_abs_diff_eq!(s / scale, expected, epsilon = 10.0 * EPS);
+    }
+
+    let (U, sigma, Vt) = (scale * B).svd();
+    assert!((U.transpose() * U).abs_diff_eq(DMat4::IDENTITY, EPS));
+    assert!((Vt * Vt.transpose()).abs_diff_eq(DMat4::IDENTITY, EPS));
+    let S = DMat4::from_diagonal(DVec4::from(sigma) / scale);
+    assert!((U * S * Vt).abs_diff_eq(B, 10.0 * EPS));
+    for (s, expected) in (scale * B).singular_values().into_iter().zip(expected_B) {
+      assert_abs_diff_eq!(s / scale, expected, epsilon = 10.0 * EPS);
+    }
+  }
+}
+
+/// Asserts that A⁺ satisfies the Penrose conditions and has the expected rank
+macro_rules! assert_pinv {
+  ($num:expr, $A:expr, $rank:expr) => {
+    paste! {{
+      let A: [< DMat $num >] = $A;
+      let (P, rank) = A.pinv(1e-10);
+      assert_eq!(rank, $rank);
+      assert!((A * P * A).abs_diff_eq(A, EPS));
+      assert!((P * A * P).abs_diff_eq(P, EPS));
+      assert!((A * P).abs_diff_eq((A * P).transpose(), EPS));
+      assert!((P * A).abs_diff_eq((P * A).transpose(), EPS));
+    }}
+  };
+}
+
+#[test]
+fn test_pinv() {
+  assert_pinv!(2, DMat2::ZERO, 0);
+  assert_pinv!(3, DMat3::ZERO, 0);
+  assert_pinv!(4, DMat4::ZERO, 0);
+  assert_eq!(DMat3::ZERO.pinv(1e-10).0, DMat3::ZERO);
+
+  assert_pinv!(2, DMat2::from_cols_array(&[1.0, 2.0, 2.0, 4.0]), 1);
+  let v = dvec3(1.0, -2.0, 0.5);
+  assert_pinv!(3, DMat3::from_cols(v, 2.0 * v, -v), 1);
+  assert_pinv!(
+    4,
+    DMat4::from_cols_array(&[
+      1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    ]),
+    2
+  );
+
+  // Regular matrices have their inverse as pseudo-inverse
+  let A = DMat3::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+  assert_pinv!(3, A, 3);
+  assert!(A.pinv(1e-10).0.abs_diff_eq(A.inverse(), EPS));
+
+  // Singular values below the cutoff are truncated
+  let A = DMat3::from_diagonal(dvec3(2.0, 1e-9, -4.0));
+  let (P, rank) = A.pinv(1e-6);
+  assert_eq!(rank, 2);
+  assert!(P.abs_diff_eq(DMat3::from_diagonal(dvec3(0.5, 0.0, -0.25)), EPS));
+  assert_eq!(A.pinv(1e-12).1, 3);
+}
+
+macro_rules! test_svd_rand {
+  ($num:expr) => {
+    paste! {
+      #[test]
+      fn [< test_svd $num _rand >]() {
+        let mut rng = Rng::with_seed(SEED);
+
+        for _ in 0..N_TESTS {
+          let v: Vec<_> = (0..$num * $num).map(|_| rng.f64() - 0.5).collect();
+          assert_svd!($num, [< DMat $num >]::from_cols_slice(&v));
+
+          // Products of random vectors have rank 1
+          let u = [< DVec $num >]::from_slice(&v[..$num]);
+          let w = [< DVec $num >]::from_slice(&v[$num..2 * $num]);
+          let A = [< DMat $num >]::from_cols_array_2d(&w.to_array().map(|x| (x * u).to_array()));
+          assert_pinv!($num, A, 1);
+        }
+      }
+    }
+  };
+}
+
+test_svd_rand!(2);
+test_svd_rand!(3);
+test_svd_rand!(4);
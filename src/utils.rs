@@ -1,42 +1,240 @@
-use glam::{dvec2, dvec3, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4};
+use crate::Complex;
 
-// Mock complex number implementation via a 2d vector
-#[inline]
-pub(crate) fn complex(x: f64) -> DVec2 {
-  dvec2(x, 0.0)
+use glam::{dvec2, dvec3, dvec4, DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, Vec4Swizzles};
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// The operations the algorithms on arrays of columns need from the scalar types f32 and f64
+pub(crate) trait Real:
+  Copy
+  + Debug
+  + Default
+  + PartialOrd
+  + Add<Output = Self>
+  + Sub<Output = Self>
+  + Mul<Output = Self>
+  + Div<Output = Self>
+  + Neg<Output = Self>
+  + AddAssign
+  + SubAssign
+  + MulAssign
+  + DivAssign
+  + Sum
+{
+  const ZERO: Self;
+  const ONE: Self;
+  const EPSILON: Self;
+  const MIN_POSITIVE: Self;
+  const INFINITY: Self;
+  const NAN: Self;
+
+  // Rounds to the nearest value, which is exact for the small constants of the algorithms
+  fn from_f64(x: f64) -> Self;
+
+  fn to_f64(self) -> f64;
+
+  fn abs(self) -> Self;
+  fn sqrt(self) -> Self;
+  fn recip(self) -> Self;
+  fn signum(self) -> Self;
+  fn copysign(self, sign: Self) -> Self;
+  fn hypot(self, other: Self) -> Self;
+  fn max(self, other: Self) -> Self;
+  fn is_finite(self) -> bool;
+  fn is_nan(self) -> bool;
+  fn total_cmp(&self, other: &Self) -> Ordering;
 }
 
-#[inline]
-pub(crate) fn csqrt(x: f64) -> DVec2 {
-  if x.is_sign_positive() {
-    dvec2(x.sqrt(), 0.0)
-  } else {
-    dvec2(0.0, (-x).sqrt())
-  }
+macro_rules! impl_real {
+  ($T:ident) => {
+    impl Real for $T {
+      const ZERO: Self = 0.0;
+      const ONE: Self = 1.0;
+      const EPSILON: Self = $T::EPSILON;
+      const MIN_POSITIVE: Self = $T::MIN_POSITIVE;
+      const INFINITY: Self = $T::INFINITY;
+      const NAN: Self = $T::NAN;
+
+      #[inline]
+      fn from_f64(x: f64) -> Self {
+        x as $T
+      }
+
+      #[inline]
+      fn to_f64(self) -> f64 {
+        self.into()
+      }
+
+      #[inline]
+      fn abs(self) -> Self {
+        $T::abs(self)
+      }
+
+      #[inline]
+      fn sqrt(self) -> Self {
+        $T::sqrt(self)
+      }
+
+      #[inline]
+      fn recip(self) -> Self {
+        $T::recip(self)
+      }
+
+      #[inline]
+      fn signum(self) -> Self {
+        $T::signum(self)
+      }
+
+      #[inline]
+      fn copysign(self, sign: Self) -> Self {
+        $T::copysign(self, sign)
+      }
+
+      #[inline]
+      fn hypot(self, other: Self) -> Self {
+        $T::hypot(self, other)
+      }
+
+      #[inline]
+      fn max(self, other: Self) -> Self {
+        $T::max(self, other)
+      }
+
+      #[inline]
+      fn is_finite(self) -> bool {
+        $T::is_finite(self)
+      }
+
+      #[inline]
+      fn is_nan(self) -> bool {
+        $T::is_nan(self)
+      }
+
+      #[inline]
+      fn total_cmp(&self, other: &Self) -> Ordering {
+        $T::total_cmp(self, other)
+      }
+    }
+  };
 }
 
-// These left-multiplication functions should be replaced with https://github.com/bitshifter/glam-rs/issues/494
+impl_real!(f32);
+impl_real!(f64);
+
 #[inline]
-pub(crate) fn lmul2(v: DVec2, M: DMat2) -> DVec2 {
-  dvec2(v.dot(M.x_axis), v.dot(M.y_axis))
+pub(crate) fn complex<T: Real>(x: T) -> Complex<T> {
+  Complex::new(x, T::ZERO)
 }
 
-#[inline]
-pub(crate) fn lmul3(v: DVec3, M: DMat3) -> DVec3 {
-  dvec3(v.dot(M.x_axis), v.dot(M.y_axis), v.dot(M.z_axis))
+// The helpers on glam types, generated for the f64 types here and for the f32 types in the single module
+macro_rules! impl_glam_utils {
+  (
+    $Mat2:ident, $Mat3:ident, $Mat4:ident, $Vec2:ident, $Vec3:ident, $Vec4:ident, $vec2:ident, $vec3:ident,
+    $vec4:ident
+  ) => {
+    // These left-multiplication functions should be replaced with https://github.com/bitshifter/glam-rs/issues/494
+    #[inline]
+    pub(crate) fn lmul2(v: $Vec2, M: $Mat2) -> $Vec2 {
+      $vec2(v.dot(M.x_axis), v.dot(M.y_axis))
+    }
+
+    #[inline]
+    pub(crate) fn lmul3(v: $Vec3, M: $Mat3) -> $Vec3 {
+      $vec3(v.dot(M.x_axis), v.dot(M.y_axis), v.dot(M.z_axis))
+    }
+
+    #[inline]
+    pub(crate) fn outer_product2(a: $Vec2, b: $Vec2) -> $Mat2 {
+      $Mat2::from_cols(a * b.x, a * b.y)
+    }
+
+    #[inline]
+    pub(crate) fn outer_product3(a: $Vec3, b: $Vec3) -> $Mat3 {
+      $Mat3::from_cols(a * b.x, a * b.y, a * b.z)
+    }
+
+    // Only the f64 householder4 reflects in four dimensions
+    #[allow(dead_code)]
+    #[inline]
+    pub(crate) fn outer_product4(a: $Vec4, b: $Vec4) -> $Mat4 {
+      $Mat4::from_cols(a * b.x, a * b.y, a * b.z, a * b.w)
+    }
+
+    // These functions embed a matrix as the lower right block of a larger identity matrix
+    // The upper left embeddings are given by from_mat2 and from_mat3
+    #[inline]
+    pub(crate) fn embed_lower2_3(M: $Mat2) -> $Mat3 {
+      $Mat3::from_cols(
+        $Vec3::X,
+        $vec3(0.0, M.x_axis.x, M.x_axis.y),
+        $vec3(0.0, M.y_axis.x, M.y_axis.y),
+      )
+    }
+
+    #[inline]
+    pub(crate) fn embed_lower2_4(M: $Mat2) -> $Mat4 {
+      block_diag2($Mat2::IDENTITY, M)
+    }
+
+    #[inline]
+    pub(crate) fn embed_lower3_4(M: $Mat3) -> $Mat4 {
+      $Mat4::from_cols(
+        $Vec4::X,
+        M.x_axis.extend(0.0).wxyz(),
+        M.y_axis.extend(0.0).wxyz(),
+        M.z_axis.extend(0.0).wxyz(),
+      )
+    }
+
+    /// Builds the block diagonal matrix diag(A, B)
+    #[inline]
+    pub(crate) fn block_diag2(A: $Mat2, B: $Mat2) -> $Mat4 {
+      $Mat4::from_cols(
+        $vec4(A.x_axis.x, A.x_axis.y, 0.0, 0.0),
+        $vec4(A.y_axis.x, A.y_axis.y, 0.0, 0.0),
+        $vec4(0.0, 0.0, B.x_axis.x, B.x_axis.y),
+        $vec4(0.0, 0.0, B.y_axis.x, B.y_axis.y),
+      )
+    }
+  };
 }
 
+pub(crate) use impl_glam_utils;
+
+impl_glam_utils!(DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, dvec2, dvec3, dvec4);
+
+/// Applies the rotation (x, y) <- (c x - s y, s x + c y) to the columns x = M[p] and y = M[q], where p < q
 #[inline]
-pub(crate) fn outer_product2(a: DVec2, b: DVec2) -> DMat2 {
-  DMat2::from_cols(a * b.x, a * b.y)
+pub(crate) fn rotate_cols<T: Real, const N: usize>(
+  M: &mut [[T; N]; N],
+  p: usize,
+  q: usize,
+  c: T,
+  s: T,
+) {
+  let (left, right) = M.split_at_mut(q);
+  for (x, y) in left[p].iter_mut().zip(right[0].iter_mut()) {
+    (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+  }
 }
 
 #[inline]
-pub(crate) fn outer_product3(a: DVec3, b: DVec3) -> DMat3 {
-  DMat3::from_cols(a * b.x, a * b.y, a * b.z)
+pub(crate) fn dot<T: Real, const N: usize>(x: &[T; N], y: &[T; N]) -> T {
+  x.iter().zip(y).map(|(&x, &y)| x * y).sum()
 }
 
-#[inline]
-pub(crate) fn outer_product4(a: DVec4, b: DVec4) -> DMat4 {
-  DMat4::from_cols(a * b.x, a * b.y, a * b.z, a * b.w)
+/// The scale by which a matrix is divided so that its largest entry is one, given that entry's magnitude
+// Squaring the entries of the normalized matrix can neither overflow nor underflow, so the discriminants,
+// reflector lengths, dot products and QR iterations built on them stay in range, and the results are scaled
+// back afterwards. The zero matrix is left as is and non-finite entries are passed through for the caller
+// to fail on
+pub(crate) fn normalization<T: Real>(norm_max: T) -> T {
+  if norm_max > T::ZERO && norm_max.is_finite() {
+    norm_max
+  } else {
+    T::ONE
+  }
 }